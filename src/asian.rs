@@ -0,0 +1,115 @@
+//! module with Asian (average price) options pricing: geometric average in closed form and
+//! arithmetic average with Turnbull-Wakeman (continuous averaging) and Levy (discrete fixings) lognormal approximations.
+//! Averaging always ends at option expiry and may be partially fixed already.
+use rv::prelude::*;
+use crate::black_scholes::*;
+
+/// Cost of carry below which it is treated as zero in moment formulas
+const SMALL_CARRY: f64 = 1e-8;
+
+/// Continuous averaging period of the option
+#[derive(Debug, Clone, Copy)]
+pub struct AveragingParams {
+    /// time from now to the start of averaging in years, negative if averaging has already started
+    pub averaging_start: f64,
+    /// average observed since the start of averaging (geometric for geometric options),
+    /// not used if averaging has not started
+    pub realized_average: f64
+}
+
+/// Geometric average price option premium in closed form (Kemna and Vorst for averaging over whole option life)
+pub fn geometric_asian_premium(is_call: bool, bs_params: &BlackScholesParams, averaging: &AveragingParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let n: Gaussian = Gaussian::standard();
+    let t = bs_params.time_to_expiry;
+    let var = bs_params.vol * bs_params.vol;
+    let carry = bs_params.rate - bs_params.div_yield;
+    let (start, w) = remaining_window(t, averaging);
+
+    // log of remaining geometric average is normal
+    let mean = bs_params.price.ln() + (carry - 0.5 * var) * (start + t) / 2.0;
+    let v = var * (start + (t - start) / 3.0);
+    let realized = if w < 1.0 {(1.0 - w) * averaging.realized_average.ln()} else {0.0};
+    let (m, v) = (realized + w * mean, w * w * v);
+
+    let sd = v.sqrt();
+    let d1 = (m - bs_params.strike.ln() + v) / sd;
+    let d2 = d1 - sd;
+    (-bs_params.rate * t).exp() * sign * ((m + 0.5 * v).exp() * n.cdf(&(sign * d1)) - bs_params.strike * n.cdf(&(sign * d2)))
+}
+
+/// Arithmetic average price option premium with continuous averaging (Turnbull and Wakeman approximation)
+pub fn turnbull_wakeman_premium(is_call: bool, bs_params: &BlackScholesParams, averaging: &AveragingParams) -> f64 {
+    let t = bs_params.time_to_expiry;
+    let var = bs_params.vol * bs_params.vol;
+    let mut b = bs_params.rate - bs_params.div_yield;
+    if b.abs() < SMALL_CARRY {
+        b = SMALL_CARRY;
+    }
+    let (tau, w) = remaining_window(t, averaging);
+
+    // first two moments of remaining average divided by spot powers
+    let m1 = ((b * t).exp() - (b * tau).exp()) / (b * (t - tau));
+    let m2 = 2.0 * ((2.0 * b + var) * t).exp() / ((b + var) * (2.0 * b + var) * (t - tau).powi(2))
+        + 2.0 * ((2.0 * b + var) * tau).exp() / (b * (t - tau).powi(2)) * (1.0 / (2.0 * b + var) - (b * (t - tau)).exp() / (b + var));
+
+    let s = bs_params.price;
+    lognormal_average_premium(is_call, bs_params, s * m1, s * s * m2, w, averaging.realized_average)
+}
+
+/// Arithmetic average price option premium with discrete fixings (Levy approximation)
+/// * fixing_times - times from now of remaining fixings in years, the last one is usually option expiry
+/// * realized_average - average of already observed fixings
+/// * realized_fixings - number of already observed fixings
+///
+/// If all fixings are observed (`fixing_times` is empty), premium is discounted payoff of `realized_average`.
+pub fn levy_premium(is_call: bool, bs_params: &BlackScholesParams, fixing_times: &[f64], realized_average: f64, realized_fixings: usize) -> f64 {
+    if fixing_times.is_empty() {
+        let sign = if is_call {1.0} else {-1.0};
+        return (-bs_params.rate * bs_params.time_to_expiry).exp() * (sign * (realized_average - bs_params.strike)).max(0.0);
+    }
+    let total = fixing_times.len() + realized_fixings;
+    let w = fixing_times.len() as f64 / total as f64;
+    let s = bs_params.price;
+    let var = bs_params.vol * bs_params.vol;
+    let b = bs_params.rate - bs_params.div_yield;
+    let m = fixing_times.len() as f64;
+
+    let first = fixing_times.iter().map(|t| s * (b * t).exp()).sum::<f64>() / m;
+    let mut second = 0.0;
+    for ti in fixing_times.iter() {
+        for tj in fixing_times.iter() {
+            second += s * s * (b * (ti + tj) + var * ti.min(*tj)).exp();
+        }
+    }
+    second /= m * m;
+
+    lognormal_average_premium(is_call, bs_params, first, second, w, realized_average)
+}
+
+/// Time of averaging start (from now, not negative) and fraction of averaging period remaining
+#[inline]
+fn remaining_window(t: f64, averaging: &AveragingParams) -> (f64, f64) {
+    let start = averaging.averaging_start;
+    if start >= 0.0 {(start, 1.0)} else {(0.0, t / (t - start))}
+}
+
+/// Prices average option approximating remaining average with lognormal distribution with given first two moments.
+/// Already fixed part of the average with weight `1 - w` shifts the strike.
+fn lognormal_average_premium(is_call: bool, bs_params: &BlackScholesParams, first: f64, second: f64, w: f64, realized: f64) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let n: Gaussian = Gaussian::standard();
+    let discount = (-bs_params.rate * bs_params.time_to_expiry).exp();
+    let strike = (bs_params.strike - (1.0 - w) * realized) / w;
+
+    if strike <= 0.0 {
+        // option will be exercised for sure (call) or expire worthless (put)
+        return if is_call {discount * w * (first - strike)} else {0.0};
+    }
+
+    let v = (second / (first * first)).ln();
+    let sd = v.sqrt();
+    let d1 = ((first / strike).ln() + 0.5 * v) / sd;
+    let d2 = d1 - sd;
+    discount * w * sign * (first * n.cdf(&(sign * d1)) - strike * n.cdf(&(sign * d2)))
+}
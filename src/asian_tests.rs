@@ -0,0 +1,84 @@
+#![cfg(test)]
+use crate::black_scholes;
+use crate::black_scholes::BlackScholesParams;
+use crate::asian::*;
+use crate::monte_carlo::*;
+
+fn bs_params() -> BlackScholesParams {
+    BlackScholesParams { price: 100.0, strike: 100.0, rate: 0.05, div_yield: 0.02, vol: 0.3, time_to_expiry: 1.0 }
+}
+
+const WHOLE_LIFE: AveragingParams = AveragingParams { averaging_start: 0.0, realized_average: 0.0 };
+
+#[test]
+fn test_geometric_kemna_vorst() {
+    let p = bs_params();
+    // geometric average is lognormal with vol / sqrt(3) and carry (b - vol^2 / 6) / 2
+    let carry = 0.5 * (p.rate - p.div_yield - p.vol * p.vol / 6.0);
+    let adjusted = BlackScholesParams { vol: p.vol / 3f64.sqrt(), div_yield: p.rate - carry, ..p };
+
+    assert!((geometric_asian_premium(true, &p, &WHOLE_LIFE) - black_scholes::call_premium(&adjusted)).abs() < 1e-10);
+    assert!((geometric_asian_premium(false, &p, &WHOLE_LIFE) - black_scholes::put_premium(&adjusted)).abs() < 1e-10);
+}
+
+#[test]
+fn test_arithmetic_against_monte_carlo() {
+    let p = bs_params();
+    let steps = 12;
+    let fixings: Vec<f64> = (1..=steps).map(|i| i as f64 / steps as f64).collect();
+    let mc_params = McParams { paths: 40000, steps, seed: 21, antithetic: true, control_variate: false };
+
+    let mc_call = mc_premium(&p, &mc_params, |path| (path[1..].iter().sum::<f64>() / steps as f64 - p.strike).max(0.0));
+    let levy_call = levy_premium(true, &p, &fixings, 0.0, 0);
+    assert!((levy_call - mc_call.premium).abs() < 3.0 * mc_call.std_error + 0.05, "{} vs {:?}", levy_call, mc_call);
+
+    let mc_put = mc_premium(&p, &mc_params, |path| (p.strike - path[1..].iter().sum::<f64>() / steps as f64).max(0.0));
+    let levy_put = levy_premium(false, &p, &fixings, 0.0, 0);
+    assert!((levy_put - mc_put.premium).abs() < 3.0 * mc_put.std_error + 0.05, "{} vs {:?}", levy_put, mc_put);
+
+    // arithmetic average call is more expensive than geometric one and cheaper than vanilla
+    let tw = turnbull_wakeman_premium(true, &p, &WHOLE_LIFE);
+    assert!(tw > geometric_asian_premium(true, &p, &WHOLE_LIFE));
+    assert!(tw < black_scholes::call_premium(&p));
+}
+
+#[test]
+fn test_turnbull_wakeman_is_limit_of_levy() {
+    let p = bs_params();
+    let fixings: Vec<f64> = (1..=2000).map(|i| i as f64 / 2000.0).collect();
+    let tw = turnbull_wakeman_premium(true, &p, &WHOLE_LIFE);
+    assert!((tw - levy_premium(true, &p, &fixings, 0.0, 0)).abs() < 0.01);
+
+    // forward starting averaging
+    let late = AveragingParams { averaging_start: 0.5, realized_average: 0.0 };
+    let late_fixings: Vec<f64> = (1..=1000).map(|i| 0.5 + i as f64 / 2000.0).collect();
+    assert!((turnbull_wakeman_premium(false, &p, &late) - levy_premium(false, &p, &late_fixings, 0.0, 0)).abs() < 0.01);
+}
+
+#[test]
+fn test_partially_fixed_average() {
+    // half of averaging period has passed with realized average of 110
+    let p = BlackScholesParams { time_to_expiry: 0.5, ..bs_params() };
+    let averaging = AveragingParams { averaging_start: -0.5, realized_average: 110.0 };
+    let steps = 6;
+    let mc_params = McParams { paths: 40000, steps, seed: 5, antithetic: true, control_variate: false };
+    let mc = mc_premium(&p, &mc_params, |path| {
+        let avg = 0.5 * 110.0 + 0.5 * path[1..].iter().sum::<f64>() / steps as f64;
+        (avg - p.strike).max(0.0)
+    });
+    let fixings: Vec<f64> = (1..=steps).map(|i| 0.5 * i as f64 / steps as f64).collect();
+    let levy = levy_premium(true, &p, &fixings, 110.0, steps);
+    assert!((levy - mc.premium).abs() < 3.0 * mc.std_error + 0.05, "{} vs {:?}", levy, mc);
+    assert!(turnbull_wakeman_premium(true, &p, &averaging) > turnbull_wakeman_premium(true, &p, &WHOLE_LIFE));
+
+    // realized average is so high that put certainly expires worthless
+    let deep = AveragingParams { averaging_start: -0.5, realized_average: 250.0 };
+    assert_eq!(turnbull_wakeman_premium(false, &p, &deep), 0.0);
+    assert!(geometric_asian_premium(false, &p, &deep) < 1e-6);
+
+    // all fixings are observed, payoff is known
+    let discount = (-p.rate * p.time_to_expiry).exp();
+    assert!((levy_premium(true, &p, &[], 110.0, steps) - 10.0 * discount).abs() < 1e-12);
+    assert_eq!(levy_premium(false, &p, &[], 110.0, steps), 0.0);
+    assert!((levy_premium(false, &p, &[], 95.0, steps) - 5.0 * discount).abs() < 1e-12);
+}
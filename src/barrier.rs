@@ -0,0 +1,188 @@
+//! module with barrier options pricing: single barrier knock-in/knock-out options with rebates (Reiner and Rubinstein),
+//! double barrier knock-out/knock-in options (Ikeda and Kunitomo) and Broadie, Glasserman and Kou correction
+//! for discretely monitored barriers.
+//!
+//! # Example
+//! ```
+//! use ivol::black_scholes::*;
+//! use ivol::barrier::*;
+//!
+//! let bs_params = BlackScholesParams{price: 100.0, strike: 100.0, div_yield: 0.04, rate: 0.08, vol: 0.25, time_to_expiry: 0.5};
+//! let barrier = BarrierParams{barrier_type: BarrierType::DownAndOut, barrier: 95.0, rebate: 0.0, monitoring_interval: 0.0};
+//! let knock_in = BarrierParams{barrier_type: BarrierType::DownAndIn, ..barrier};
+//!
+//! let out_call = barrier_premium(true, &bs_params, &barrier);
+//! let in_call = barrier_premium(true, &bs_params, &knock_in);
+//! assert!((out_call + in_call - call_premium(&bs_params)).abs() < 1e-10);
+//! ```
+use rv::prelude::*;
+use crate::black_scholes::*;
+
+/// Broadie, Glasserman and Kou constant `-zeta(1/2) / sqrt(2 pi)`
+const BGK_BETA: f64 = 0.5826;
+/// Number of terms on each side of Ikeda and Kunitomo series
+const DOUBLE_BARRIER_TERMS: i32 = 10;
+
+/// Type of single barrier
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarrierType {
+    DownAndIn,
+    DownAndOut,
+    UpAndIn,
+    UpAndOut
+}
+
+impl BarrierType {
+
+    pub fn is_up(&self) -> bool {
+        matches!(self, BarrierType::UpAndIn | BarrierType::UpAndOut)
+    }
+
+    pub fn is_knock_in(&self) -> bool {
+        matches!(self, BarrierType::DownAndIn | BarrierType::UpAndIn)
+    }
+}
+
+/// Parameters of single barrier
+#[derive(Debug, Clone, Copy)]
+pub struct BarrierParams {
+    pub barrier_type: BarrierType,
+    /// barrier level
+    pub barrier: f64,
+    /// cash rebate, paid at expiry for knock-in options which were not knocked in
+    /// and at the hit time for knocked out options
+    pub rebate: f64,
+    /// time between barrier observations in years, zero for continuous monitoring
+    pub monitoring_interval: f64
+}
+
+/// Parameters of double barrier
+#[derive(Debug, Clone, Copy)]
+pub struct DoubleBarrierParams {
+    /// lower barrier level
+    pub lower: f64,
+    /// upper barrier level
+    pub upper: f64,
+    /// time between barrier observations in years, zero for continuous monitoring
+    pub monitoring_interval: f64
+}
+
+/// Barrier level shifted away from spot to price discretely monitored barrier with continuous monitoring formula
+/// (Broadie, Glasserman and Kou)
+pub fn bgk_adjusted_barrier(barrier: f64, is_up: bool, vol: f64, monitoring_interval: f64) -> f64 {
+    let sign = if is_up {1.0} else {-1.0};
+    barrier * (sign * BGK_BETA * vol * monitoring_interval.sqrt()).exp()
+}
+
+/// Single barrier option premium (Reiner and Rubinstein formulas)
+pub fn barrier_premium(is_call: bool, bs_params: &BlackScholesParams, barrier_params: &BarrierParams) -> f64 {
+    let is_up = barrier_params.barrier_type.is_up();
+    let h = bgk_adjusted_barrier(barrier_params.barrier, is_up, bs_params.vol, barrier_params.monitoring_interval);
+    let s = bs_params.price;
+    let x = bs_params.strike;
+    let rebate = barrier_params.rebate;
+
+    // barrier is already breached
+    if (is_up && s >= h) || (!is_up && s <= h) {
+        return if barrier_params.barrier_type.is_knock_in() {
+            generic_black_scholes(is_call, bs_params)
+        } else {
+            rebate
+        };
+    }
+
+    let n: Gaussian = Gaussian::standard();
+    let t = bs_params.time_to_expiry;
+    let r = bs_params.rate;
+    let var = bs_params.vol * bs_params.vol;
+    let vt = bs_params.vol * t.sqrt();
+    let b = r - bs_params.div_yield;
+    let mu = (b - var / 2.0) / var;
+    let lambda = (mu * mu + 2.0 * r / var).sqrt();
+    let phi = if is_call {1.0} else {-1.0};
+    let eta = if is_up {-1.0} else {1.0};
+
+    let x1 = (s / x).ln() / vt + (1.0 + mu) * vt;
+    let x2 = (s / h).ln() / vt + (1.0 + mu) * vt;
+    let y1 = (h * h / (s * x)).ln() / vt + (1.0 + mu) * vt;
+    let y2 = (h / s).ln() / vt + (1.0 + mu) * vt;
+    let z = (h / s).ln() / vt + lambda * vt;
+
+    let dprice = s * ((b - r) * t).exp();
+    let dstrike = x * (-r * t).exp();
+    let hs = h / s;
+
+    let a = phi * dprice * n.cdf(&(phi * x1)) - phi * dstrike * n.cdf(&(phi * x1 - phi * vt));
+    let bb = phi * dprice * n.cdf(&(phi * x2)) - phi * dstrike * n.cdf(&(phi * x2 - phi * vt));
+    let c = phi * dprice * hs.powf(2.0 * (mu + 1.0)) * n.cdf(&(eta * y1)) - phi * dstrike * hs.powf(2.0 * mu) * n.cdf(&(eta * y1 - eta * vt));
+    let d = phi * dprice * hs.powf(2.0 * (mu + 1.0)) * n.cdf(&(eta * y2)) - phi * dstrike * hs.powf(2.0 * mu) * n.cdf(&(eta * y2 - eta * vt));
+    let e = rebate * (-r * t).exp() * (n.cdf(&(eta * x2 - eta * vt)) - hs.powf(2.0 * mu) * n.cdf(&(eta * y2 - eta * vt)));
+    let f = rebate * (hs.powf(mu + lambda) * n.cdf(&(eta * z)) + hs.powf(mu - lambda) * n.cdf(&(eta * z - 2.0 * eta * lambda * vt)));
+
+    let above = x > h;
+    match (barrier_params.barrier_type, is_call) {
+        (BarrierType::DownAndIn, true) => if above {c + e} else {a - bb + d + e},
+        (BarrierType::UpAndIn, true) => if above {a + e} else {bb - c + d + e},
+        (BarrierType::DownAndIn, false) => if above {bb - c + d + e} else {a + e},
+        (BarrierType::UpAndIn, false) => if above {a - bb + d + e} else {c + e},
+        (BarrierType::DownAndOut, true) => if above {a - c + f} else {bb - d + f},
+        (BarrierType::UpAndOut, true) => if above {f} else {a - bb + c - d + f},
+        (BarrierType::DownAndOut, false) => if above {a - bb + c - d + f} else {f},
+        (BarrierType::UpAndOut, false) => if above {bb - d + f} else {a - c + f}
+    }
+}
+
+/// Double barrier option premium with flat barriers (Ikeda and Kunitomo series).
+/// Knock-in option is priced as vanilla less knock-out.
+pub fn double_barrier_premium(is_call: bool, is_knock_in: bool, bs_params: &BlackScholesParams, barrier_params: &DoubleBarrierParams) -> f64 {
+    let vanilla = generic_black_scholes(is_call, bs_params);
+    let knock_out = double_knock_out_premium(is_call, bs_params, barrier_params);
+    if is_knock_in {vanilla - knock_out} else {knock_out}
+}
+
+fn double_knock_out_premium(is_call: bool, bs_params: &BlackScholesParams, barrier_params: &DoubleBarrierParams) -> f64 {
+    let l = bgk_adjusted_barrier(barrier_params.lower, false, bs_params.vol, barrier_params.monitoring_interval);
+    let u = bgk_adjusted_barrier(barrier_params.upper, true, bs_params.vol, barrier_params.monitoring_interval);
+    let s = bs_params.price;
+    let x = bs_params.strike;
+    if s <= l || s >= u || (is_call && x >= u) || (!is_call && x <= l) {
+        return 0.0;
+    }
+
+    let n: Gaussian = Gaussian::standard();
+    let t = bs_params.time_to_expiry;
+    let var = bs_params.vol * bs_params.vol;
+    let vt = bs_params.vol * t.sqrt();
+    let b = bs_params.rate - bs_params.div_yield;
+    let mu1 = 2.0 * b / var + 1.0;
+    let mu3 = mu1;
+    let drift = (b + var / 2.0) * t;
+    let d = |num: f64| (num.ln() + drift) / vt;
+
+    // for a call payoff is cut by upper barrier, for a put by lower barrier
+    let (k_lo, k_hi) = if is_call {(x, u)} else {(l, x)};
+
+    let mut asset = 0.0;
+    let mut cash = 0.0;
+    for i in -DOUBLE_BARRIER_TERMS..=DOUBLE_BARRIER_TERMS {
+        let nf = i as f64;
+        let ul = (u / l).powf(nf);
+        let refl = l.powf(nf + 1.0) / (u.powf(nf) * s);
+        let d1 = d(s * ul * ul / k_lo);
+        let d2 = d(s * ul * ul / k_hi);
+        let d3 = d(refl * refl * s / k_lo);
+        let d4 = d(refl * refl * s / k_hi);
+
+        asset += ul.powf(mu1) * (n.cdf(&d1) - n.cdf(&d2)) - refl.powf(mu3) * (n.cdf(&d3) - n.cdf(&d4));
+        cash += ul.powf(mu1 - 2.0) * (n.cdf(&(d1 - vt)) - n.cdf(&(d2 - vt)))
+            - refl.powf(mu3 - 2.0) * (n.cdf(&(d3 - vt)) - n.cdf(&(d4 - vt)));
+    }
+
+    let dprice = s * (-bs_params.div_yield * t).exp();
+    let dstrike = x * (-bs_params.rate * t).exp();
+    if is_call {
+        dprice * asset - dstrike * cash
+    } else {
+        dstrike * cash - dprice * asset
+    }
+}
@@ -0,0 +1,110 @@
+#![cfg(test)]
+use crate::black_scholes;
+use crate::black_scholes::BlackScholesParams;
+use crate::barrier::*;
+use crate::monte_carlo::*;
+
+const EPS: f64 = 0.0001;
+
+/// market data of Haug's barrier options table
+fn haug_params(strike: f64) -> BlackScholesParams {
+    BlackScholesParams { price: 100.0, strike, rate: 0.08, div_yield: 0.04, vol: 0.25, time_to_expiry: 0.5 }
+}
+
+fn barrier(barrier_type: BarrierType, level: f64) -> BarrierParams {
+    BarrierParams { barrier_type, barrier: level, rebate: 3.0, monitoring_interval: 0.0 }
+}
+
+#[test]
+fn test_single_barrier_reference_values() {
+    let cases = [
+        (true, BarrierType::DownAndOut, 95.0, [9.0246, 6.7924, 4.8759]),
+        (true, BarrierType::UpAndOut, 105.0, [2.6789, 2.3580, 2.3453]),
+        (true, BarrierType::DownAndIn, 95.0, [7.7627, 4.0109, 2.0576]),
+        (true, BarrierType::UpAndIn, 105.0, [14.1112, 8.4482, 4.5910]),
+        (false, BarrierType::DownAndOut, 95.0, [2.2798, 2.2947, 2.6252]),
+        (false, BarrierType::UpAndOut, 105.0, [3.7760, 5.4932, 7.5187]),
+        (false, BarrierType::DownAndIn, 95.0, [2.9586, 6.5677, 11.9752]),
+        (false, BarrierType::UpAndIn, 105.0, [1.4653, 3.3721, 7.0846])
+    ];
+
+    for (is_call, barrier_type, level, expected) in cases.iter() {
+        for (strike, value) in [90.0, 100.0, 110.0].iter().zip(expected.iter()) {
+            let premium = barrier_premium(*is_call, &haug_params(*strike), &barrier(*barrier_type, *level));
+            assert!((premium - value).abs() < 0.001, "{:?} call={} strike={}: {} vs {}", barrier_type, is_call, strike, premium, value);
+        }
+    }
+}
+
+#[test]
+fn test_in_out_parity() {
+    for is_call in [true, false].iter() {
+        for (in_type, out_type, level) in [(BarrierType::DownAndIn, BarrierType::DownAndOut, 92.0), (BarrierType::UpAndIn, BarrierType::UpAndOut, 108.0)].iter() {
+            for strike in [85.0, 100.0, 115.0].iter() {
+                let p = haug_params(*strike);
+                let knock_in = barrier_premium(*is_call, &p, &BarrierParams { rebate: 0.0, ..barrier(*in_type, *level) });
+                let knock_out = barrier_premium(*is_call, &p, &BarrierParams { rebate: 0.0, ..barrier(*out_type, *level) });
+                let vanilla = if *is_call {black_scholes::call_premium(&p)} else {black_scholes::put_premium(&p)};
+                assert!((knock_in + knock_out - vanilla).abs() < EPS);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_breached_barrier() {
+    let p = haug_params(100.0);
+    assert!((barrier_premium(true, &p, &barrier(BarrierType::DownAndOut, 100.0)) - 3.0).abs() < EPS);
+    assert!((barrier_premium(true, &p, &barrier(BarrierType::DownAndIn, 100.0)) - black_scholes::call_premium(&p)).abs() < EPS);
+}
+
+#[test]
+fn test_double_barrier_reference_values() {
+    let p = BlackScholesParams { price: 100.0, strike: 100.0, rate: 0.1, div_yield: 0.0, vol: 0.15, time_to_expiry: 0.25 };
+    let cases = [(50.0, 150.0, 4.3515), (60.0, 140.0, 4.3505), (70.0, 130.0, 4.3143)];
+    for (lower, upper, value) in cases.iter() {
+        let params = DoubleBarrierParams { lower: *lower, upper: *upper, monitoring_interval: 0.0 };
+        let premium = double_barrier_premium(true, false, &p, &params);
+        assert!((premium - value).abs() < 0.001, "{} {}: {} vs {}", lower, upper, premium, value);
+
+        let knock_in = double_barrier_premium(true, true, &p, &params);
+        assert!((premium + knock_in - black_scholes::call_premium(&p)).abs() < EPS);
+    }
+
+    // wide barriers do not affect put
+    let params = DoubleBarrierParams { lower: 40.0, upper: 200.0, monitoring_interval: 0.0 };
+    assert!((double_barrier_premium(false, false, &p, &params) - black_scholes::put_premium(&p)).abs() < EPS);
+}
+
+#[test]
+fn test_discrete_monitoring_correction() {
+    let p = BlackScholesParams { price: 100.0, strike: 100.0, rate: 0.05, div_yield: 0.0, vol: 0.3, time_to_expiry: 0.5 };
+    let steps = 25;
+    let dt = p.time_to_expiry / steps as f64;
+    let params = BarrierParams { barrier_type: BarrierType::DownAndOut, barrier: 90.0, rebate: 0.0, monitoring_interval: dt };
+
+    let mc_params = McParams { paths: 40000, steps, seed: 17, antithetic: true, control_variate: true };
+    let mc = mc_premium(&p, &mc_params, |path| {
+        if path.iter().any(|s| *s <= 90.0) {0.0} else {(path[steps] - 100.0).max(0.0)}
+    });
+
+    let discrete = barrier_premium(true, &p, &params);
+    let continuous = barrier_premium(true, &p, &BarrierParams { monitoring_interval: 0.0, ..params });
+    assert!(discrete > continuous);
+    assert!((discrete - mc.premium).abs() < 3.0 * mc.std_error + 0.02, "{} vs {:?}", discrete, mc);
+}
+
+#[test]
+fn test_double_barrier_discrete_monitoring() {
+    let p = BlackScholesParams { price: 100.0, strike: 100.0, rate: 0.1, div_yield: 0.0, vol: 0.15, time_to_expiry: 0.25 };
+    let steps = 50;
+    let params = DoubleBarrierParams { lower: 90.0, upper: 110.0, monitoring_interval: p.time_to_expiry / steps as f64 };
+
+    let mc_params = McParams { paths: 20000, steps, seed: 3, antithetic: true, control_variate: false };
+    let mc = mc_premium(&p, &mc_params, |path| {
+        if path.iter().any(|s| *s <= 90.0 || *s >= 110.0) {0.0} else {(path[steps] - 100.0).max(0.0)}
+    });
+
+    let discrete = double_barrier_premium(true, false, &p, &params);
+    assert!((discrete - mc.premium).abs() < 3.0 * mc.std_error + 0.01, "{} vs {:?}", discrete, mc);
+}
@@ -0,0 +1,57 @@
+#![cfg(test)]
+use ivol::black_scholes::*;
+use crate::output::Value;
+use crate::{greek_values, implied_vol, premium_values, surface_points, ALL_GREEKS};
+
+fn bs_params() -> BlackScholesParams {
+    BlackScholesParams { price: 100.0, strike: 95.0, rate: 0.05, div_yield: 0.02, vol: 0.25, time_to_expiry: 0.5 }
+}
+
+#[test]
+fn test_greek_names() {
+    // every reported name must correspond to its own value
+    let p = bs_params();
+    let values = greek_values(&p, &[true, false], &ALL_GREEKS);
+    let expected = [
+        ("call_delta", call_delta(&p)), ("put_delta", put_delta(&p)), ("gamma", gamma(&p)), ("vega", vega(&p)),
+        ("call_theta", call_theta(&p)), ("put_theta", put_theta(&p)), ("call_rho", call_rho(&p)), ("put_rho", put_rho(&p)),
+        ("call_phi", call_phi(&p)), ("put_phi", put_phi(&p))
+    ];
+    assert_eq!(values.len(), expected.len());
+    for ((name, value), (expected_name, expected_value)) in values.iter().zip(expected.iter()) {
+        assert_eq!(name, expected_name);
+        assert_eq!(*value, Value::Number(*expected_value));
+    }
+
+    let selected = greek_values(&p, &[false], &["rho", "gamma"]);
+    assert_eq!(selected, vec![("gamma".to_string(), Value::Number(gamma(&p))), ("put_rho".to_string(), Value::Number(put_rho(&p)))]);
+}
+
+#[test]
+fn test_premium_names() {
+    let p = bs_params();
+    assert_eq!(premium_values(&p, &[true, false]), vec![
+        ("call_premium".to_string(), Value::Number(call_premium(&p))),
+        ("put_premium".to_string(), Value::Number(put_premium(&p)))
+    ]);
+}
+
+#[test]
+fn test_surface_points() {
+    let lines = |ls: &[&str]| ls.iter().map(|l| l.to_string()).collect::<Vec<String>>();
+    assert_eq!(surface_points(&lines(&["expiry,strike,vol", "0.5, 100, 0.2"])).unwrap(), vec![(0.5, 100.0, 0.2)]);
+    // NaN and infinity are valid floats for Rust parser
+    assert_eq!(surface_points(&lines(&["0.5,100,0.2", "nan,100,0.2"])), Err("line 2: expected expiry,strike,vol".to_string()));
+    assert!(surface_points(&lines(&["0.5,inf,0.2"])).is_err());
+    assert!(surface_points(&lines(&["0.5,100"])).is_err());
+}
+
+#[test]
+fn test_implied_vol_bounds() {
+    let p = BlackScholesParams { price: 100.0, strike: 100.0, rate: 0.05, div_yield: 0.0, vol: 0.0, time_to_expiry: 1.0 };
+    let premium = call_premium(&BlackScholesParams { vol: 0.3, ..p });
+    assert!((implied_vol(true, premium, &p).unwrap() - 0.3).abs() < 1e-6);
+    // premium above price or below intrinsic value must not reach the solver
+    assert_eq!(implied_vol(true, 200.0, &p), Err("premium is outside of no-arbitrage bounds".to_string()));
+    assert!(implied_vol(false, 0.0, &p).is_err());
+}
@@ -0,0 +1,486 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use clap::{Arg, App, AppSettings, ArgMatches, SubCommand};
+use ivol::black_scholes::*;
+use ivol::chain::*;
+use ivol::local_vol::*;
+use ivol::scenario::*;
+use output::*;
+use stream::*;
+
+mod output;
+mod output_tests;
+mod stream;
+mod stream_tests;
+mod cli_tests;
+
+/// Greeks printed by `greeks` subcommand when none are selected
+const ALL_GREEKS: [&str; 6] = ["delta", "gamma", "vega", "theta", "rho", "phi"];
+
+fn main() {
+
+    // parsing args
+    let matches = App::new("ivol cli")
+        .version("0.01")
+        .about("Command Line Tool for ivol module functions")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(Arg::with_name("output")
+            .short("o")
+            .long("output")
+            .value_name("FORMAT")
+            .help("output format, csv by default for chain")
+            .possible_values(&["json", "csv", "table"])
+            .default_value("table")
+            .global(true)
+            .takes_value(true))
+        .subcommand(SubCommand::with_name("price")
+            .about("call and put premiums")
+            .args(&contract_args())
+            .arg(vol_arg())
+            .arg(type_arg(false)))
+        .subcommand(SubCommand::with_name("iv")
+            .about("implied volatility from option market price")
+            .args(&contract_args())
+            .arg(type_arg(true))
+            .arg(premium_arg("option market price")))
+        .subcommand(SubCommand::with_name("greeks")
+            .about("option sensitivities")
+            .args(&contract_args())
+            .arg(vol_arg())
+            .arg(type_arg(false))
+            .arg(Arg::with_name("greeks")
+                .short("g")
+                .long("greeks")
+                .value_name("GREEKS")
+                .help("comma separated greeks to calculate, all by default")
+                .possible_values(&ALL_GREEKS)
+                .use_delimiter(true)
+                .takes_value(true)))
+        .subcommand(SubCommand::with_name("parity")
+            .about("converts call price to put price and vice versa with put/call parity")
+            .args(&contract_args())
+            .arg(type_arg(true).help("type of the given option price"))
+            .arg(premium_arg("option market price")))
+        .subcommand(SubCommand::with_name("surface")
+            .about("implied and local volatility from implied volatility surface CSV file with columns expiry,strike,vol")
+            .arg(file_arg("implied volatility surface CSV file, strikes must be the same for all expiries"))
+            .arg(float_arg("price", "p", "PRICE", "option's underlying price").required(true))
+            .arg(float_arg("rate", "r", "RATE", "risk free rate").required(true))
+            .arg(float_arg("div_yield", "d", "DIV", "annual dividend yield").long("div").default_value("0"))
+            .arg(list_arg("strikes", "STRIKES", "comma separated strikes to evaluate, surface strikes by default"))
+            .arg(list_arg("expiries", "TIMES", "comma separated times to expiry to evaluate, surface expiries by default")))
+        .subcommand(SubCommand::with_name("chain")
+            .about("implied volatility and greeks for option chain CSV file with columns \
+                    symbol,expiry,strike,type,bid,ask,last,underlying price")
+            .arg(file_arg("option chain CSV file"))
+            .arg(Arg::with_name("date")
+                .long("date")
+                .value_name("DATE")
+                .help("valuation date YYYY-MM-DD, needed when expiries are dates")
+                .takes_value(true))
+            .arg(float_arg("rate", "r", "RATE", "risk free rate").default_value("0"))
+            .arg(float_arg("div_yield", "d", "DIV", "annual dividend yield").long("div").default_value("0")))
+        .subcommand(SubCommand::with_name("scenario")
+            .about("spot and volatility ladder of option or positions from CSV file with header and columns \
+                    type, quantity, price, strike, time_to_expiry, rate, div_yield, vol or premium")
+            .args(&contract_args().into_iter().map(|a| a.required_unless("positions")).collect::<Vec<Arg>>())
+            .arg(vol_arg().required_unless("positions"))
+            .arg(type_arg(false).help("option type, call by default"))
+            .arg(float_arg("quantity", "q", "QUANTITY", "number of options, negative for short position").default_value("1"))
+            .arg(Arg::with_name("positions")
+                .long("positions")
+                .value_name("FILE")
+                .help("positions CSV file, used instead of single option arguments")
+                .takes_value(true))
+            .arg(list_arg("spot-shifts", "SHIFTS", "comma separated relative spot shifts").default_value("-0.1,-0.05,0,0.05,0.1"))
+            .arg(list_arg("vol-shifts", "SHIFTS", "comma separated absolute volatility shifts").default_value("-0.05,0,0.05"))
+            .arg(list_arg("time-shifts", "TIMES", "comma separated elapsed times in years").default_value("0")))
+        .subcommand(SubCommand::with_name("stream")
+            .about("prices contracts or solves implied volatility for records read from stdin line by line, \
+                    record fields are price, strike, time_to_expiry, rate, div_yield, type, vol or premium and id")
+            .arg(Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .value_name("FORMAT")
+                .help("input format, CSV input starts with header")
+                .possible_values(&["json", "csv"])
+                .default_value("json")
+                .takes_value(true))
+            .arg(float_arg("rate", "r", "RATE", "risk free rate for records without it").default_value("0"))
+            .arg(float_arg("div_yield", "d", "DIV", "dividend yield for records without it").long("div").default_value("0")))
+        .get_matches();
+
+    let format: Format = matches.value_of("output").unwrap().parse().unwrap();
+    // streaming subcommands write rows as they are evaluated, chain output is CSV unless format is given
+    let streamed = match matches.subcommand() {
+        ("stream", Some(m)) => Some(run_stream(m, format)),
+        ("chain", Some(m)) => Some(run_chain(m, if m.occurrences_of("output") == 0 {Format::Csv} else {format})),
+        _ => None
+    };
+    if let Some(result) = streamed {
+        if let Err(e) = result {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let result = match matches.subcommand() {
+        ("price", Some(m)) => run_price(m),
+        ("iv", Some(m)) => run_iv(m),
+        ("greeks", Some(m)) => run_greeks(m),
+        ("parity", Some(m)) => run_parity(m),
+        ("surface", Some(m)) => run_surface(m),
+        ("scenario", Some(m)) => run_scenario(m),
+        _ => unreachable!()
+    };
+    let written = result.and_then(|table| table.write(format, &mut std::io::stdout().lock()).map_err(|e| e.to_string()));
+    if let Err(e) = written {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+/// float values validator
+fn float_validator(v: String) -> Result<(), String> {
+    match v.parse::<f64>() {
+        Ok(x) if x.is_finite() => Ok(()),
+        Ok(_) => Err("value must be a finite number".to_string()),
+        Err(_) => Err("value must be a float number".to_string())
+    }
+}
+
+fn float_arg<'a>(name: &'a str, short: &'a str, value_name: &'a str, help: &'a str) -> Arg<'a, 'a> {
+    Arg::with_name(name)
+        .short(short)
+        .long(name)
+        .value_name(value_name)
+        .help(help)
+        .validator(float_validator)
+        .takes_value(true)
+}
+
+fn list_arg<'a>(name: &'a str, value_name: &'a str, help: &'a str) -> Arg<'a, 'a> {
+    Arg::with_name(name)
+        .long(name)
+        .value_name(value_name)
+        .help(help)
+        .use_delimiter(true)
+        .allow_hyphen_values(true)
+        .validator(float_validator)
+        .takes_value(true)
+}
+
+fn file_arg(help: &str) -> Arg<'_, '_> {
+    Arg::with_name("file")
+        .value_name("FILE")
+        .help(help)
+        .required(true)
+}
+
+/// Arguments describing option contract and market, without volatility
+fn contract_args<'a>() -> Vec<Arg<'a, 'a>> {
+    vec![
+        float_arg("price", "p", "PRICE", "option's underlying price").required(true),
+        float_arg("strike", "s", "STRIKE", "option's strike value").required(true),
+        float_arg("rate", "r", "RATE", "risk free rate").required(true),
+        float_arg("div_yield", "d", "DIV", "annual dividend yield").long("div").default_value("0"),
+        float_arg("time_to_expiry", "t", "TIME", "time to expiry in years").default_value("1")
+    ]
+}
+
+fn vol_arg<'a>() -> Arg<'a, 'a> {
+    float_arg("volatility", "v", "VOL", "volatility in percent (decimal)").required(true)
+}
+
+fn premium_arg(help: &str) -> Arg<'_, '_> {
+    float_arg("premium", "m", "PREMIUM", help).required(true)
+}
+
+/// Option type argument, if not required both calls and puts are evaluated by default
+fn type_arg<'a>(required: bool) -> Arg<'a, 'a> {
+    Arg::with_name("type")
+        .long("type")
+        .value_name("TYPE")
+        .help("option type")
+        .possible_values(&["call", "put"])
+        .required(required)
+        .takes_value(true)
+}
+
+fn float_value(matches: &ArgMatches, name: &str) -> f64 {
+    matches.value_of(name).unwrap().parse().unwrap()
+}
+
+fn float_values(matches: &ArgMatches, name: &str) -> Option<Vec<f64>> {
+    matches.values_of(name).map(|vs| vs.map(|v| v.parse().unwrap()).collect())
+}
+
+/// Contract parameters from arguments, volatility is zero if it is not an argument of subcommand
+fn bs_params(matches: &ArgMatches) -> BlackScholesParams {
+    BlackScholesParams {
+        price: float_value(matches, "price"),
+        strike: float_value(matches, "strike"),
+        rate: float_value(matches, "rate"),
+        div_yield: float_value(matches, "div_yield"),
+        vol: matches.value_of("volatility").map(|v| v.parse().unwrap()).unwrap_or(0.0),
+        time_to_expiry: float_value(matches, "time_to_expiry")
+    }
+}
+
+/// Option types selected with `--type`, both calls and puts if it is missing
+fn option_types(matches: &ArgMatches) -> Vec<bool> {
+    match matches.value_of("type") {
+        Some(t) => vec![t == "call"],
+        None => vec![true, false]
+    }
+}
+
+fn type_name(is_call: bool) -> &'static str {
+    if is_call {"call"} else {"put"}
+}
+
+fn premium_values(bs_params: &BlackScholesParams, option_types: &[bool]) -> Vec<(String, Value)> {
+    option_types.iter()
+        .map(|is_call| (format!("{}_premium", type_name(*is_call)), if *is_call {call_premium(bs_params)} else {put_premium(bs_params)}.into()))
+        .collect()
+}
+
+/// Selected greeks in the order of [`ALL_GREEKS`], gamma and vega are the same for calls and puts
+fn greek_values(bs_params: &BlackScholesParams, option_types: &[bool], selected: &[&str]) -> Vec<(String, Value)> {
+    let mut values = Vec::new();
+    for name in ALL_GREEKS.iter().filter(|g| selected.contains(g)) {
+        if *name == "gamma" || *name == "vega" {
+            let value = if *name == "gamma" {gamma(bs_params)} else {vega(bs_params)};
+            values.push((name.to_string(), value.into()));
+            continue;
+        }
+        for is_call in option_types.iter() {
+            let value = match (*name, *is_call) {
+                ("delta", true) => call_delta(bs_params),
+                ("delta", false) => put_delta(bs_params),
+                ("theta", true) => call_theta(bs_params),
+                ("theta", false) => put_theta(bs_params),
+                ("rho", true) => call_rho(bs_params),
+                ("rho", false) => put_rho(bs_params),
+                (_, true) => call_phi(bs_params),
+                (_, false) => put_phi(bs_params)
+            };
+            values.push((format!("{}_{}", type_name(*is_call), name), value.into()));
+        }
+    }
+    values
+}
+
+fn run_price(matches: &ArgMatches) -> Result<Table, String> {
+    Ok(Table::from_values(premium_values(&bs_params(matches), &option_types(matches))))
+}
+
+fn run_iv(matches: &ArgMatches) -> Result<Table, String> {
+    let bs_params = bs_params(matches);
+    let premium = float_value(matches, "premium");
+    let is_call = matches.value_of("type") == Some("call");
+    let vol = implied_vol(is_call, premium, &bs_params)?;
+    Ok(Table::from_values(vec![("implied_vol".to_string(), vol.into())]))
+}
+
+/// Implied volatility of premium, which must be within no-arbitrage bounds
+fn implied_vol(is_call: bool, premium: f64, bs_params: &BlackScholesParams) -> Result<f64, String> {
+    if !within_bounds(is_call, premium, bs_params) {
+        return Err("premium is outside of no-arbitrage bounds".to_string());
+    }
+    let vol = if is_call {call_impl_vol(&premium, bs_params)} else {put_impl_vol(&premium, bs_params)};
+    vol.map_err(|v| format!("implied volatility did not converge, last value is {}", v))
+}
+
+fn run_greeks(matches: &ArgMatches) -> Result<Table, String> {
+    let selected: Vec<&str> = matches.values_of("greeks").map(|vs| vs.collect()).unwrap_or_else(|| ALL_GREEKS.to_vec());
+    Ok(Table::from_values(greek_values(&bs_params(matches), &option_types(matches), &selected)))
+}
+
+fn run_parity(matches: &ArgMatches) -> Result<Table, String> {
+    let bs_params = bs_params(matches);
+    let premium = float_value(matches, "premium");
+    let is_call = matches.value_of("type") == Some("call");
+    let converted = callput_price(is_call, &premium, &bs_params);
+    Ok(Table::from_values(vec![(format!("{}_premium", type_name(!is_call)), converted.into())]))
+}
+
+/// Implied and local volatility on the grid of strikes and expiries
+fn run_surface(matches: &ArgMatches) -> Result<Table, String> {
+    let points = surface_points(&read_lines(matches.value_of("file").unwrap())?)?;
+    let mut expiries: Vec<f64> = points.iter().map(|p| p.0).collect();
+    let mut strikes: Vec<f64> = points.iter().map(|p| p.1).collect();
+    for v in [&mut expiries, &mut strikes] {
+        v.sort_by(f64::total_cmp);
+        v.dedup();
+    }
+    let mut vols = vec![vec![f64::NAN; strikes.len()]; expiries.len()];
+    for (t, k, vol) in points.iter() {
+        let i = expiries.iter().position(|e| e == t).unwrap();
+        let j = strikes.iter().position(|s| s == k).unwrap();
+        vols[i][j] = *vol;
+    }
+    if vols.iter().flatten().any(|v| v.is_nan()) {
+        return Err("surface must have volatility for every expiry and strike".to_string());
+    }
+
+    let price = float_value(matches, "price");
+    let surface = ImpliedVolSurface::new(price, float_value(matches, "rate"), float_value(matches, "div_yield"), &expiries, &strikes, &vols)?;
+    let query_strikes = float_values(matches, "strikes").unwrap_or(strikes);
+    let query_expiries = float_values(matches, "expiries").unwrap_or(expiries);
+    let mut table = Table::new(&["expiry", "strike", "implied_vol", "local_vol"]);
+    for t in query_expiries.iter() {
+        for k in query_strikes.iter() {
+            table.push(vec![(*t).into(), (*k).into(), surface.implied_vol(*k, *t).into(), local_vol(&surface, *k, *t).into()]);
+        }
+    }
+    Ok(table)
+}
+
+/// (expiry, strike, vol) points of surface file lines with optional header
+fn surface_points(lines: &[String]) -> Result<Vec<(f64, f64, f64)>, String> {
+    let mut points = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        let parsed: Result<Vec<f64>, _> = fields.iter().map(|f| f.parse::<f64>()).collect();
+        match parsed {
+            Ok(v) if v.len() == 3 && v.iter().all(|x| x.is_finite()) => points.push((v[0], v[1], v[2])),
+            // header
+            Err(_) if i == 0 => continue,
+            _ => return Err(format!("line {}: expected expiry,strike,vol", i + 1))
+        }
+    }
+    Ok(points)
+}
+
+fn read_lines(path: &str) -> Result<Vec<String>, String> {
+    let file = File::open(path).map_err(|e| format!("can't open {}: {}", path, e))?;
+    let lines: Result<Vec<String>, _> = BufReader::new(file).lines().collect();
+    let lines = lines.map_err(|e| format!("can't read {}: {}", path, e))?;
+    Ok(lines.into_iter().filter(|l| !l.trim().is_empty()).collect())
+}
+
+/// Writes implied volatilities and greeks for each record of option chain file as soon as it is read,
+/// records which can't be parsed are reported to stderr and skipped
+fn run_chain(matches: &ArgMatches, format: Format) -> Result<(), String> {
+    let path = matches.value_of("file").unwrap();
+    let date = matches.value_of("date");
+    let rate = float_value(matches, "rate");
+    let div_yield = float_value(matches, "div_yield");
+    let file = File::open(path).map_err(|e| format!("can't open {}: {}", path, e))?;
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+
+    let mut writer = RowWriter::new(format, &["symbol", "expiry", "strike", "type", "bid_vol", "mid_vol", "ask_vol", "delta", "gamma", "vega", "theta", "rho"]);
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| format!("can't read {}: {}", path, e))?;
+        if line.trim().is_empty() || (i == 0 && line.to_ascii_lowercase().contains("strike")) {
+            continue;
+        }
+        let record = match ChainRecord::from_csv(&line).and_then(|r| r.time_to_expiry(date).map(|t| (r, t))) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("line {}: {}", i + 1, e);
+                continue;
+            }
+        };
+        let (record, time_to_expiry) = record;
+        let result = record.evaluate(time_to_expiry, rate, div_yield);
+        let greeks = result.greeks;
+        let written = writer.write(&[
+            record.symbol.into(), record.expiry.into(), record.strike.into(), if record.is_call {"C"} else {"P"}.into(),
+            result.bid_vol.into(), result.mid_vol.into(), result.ask_vol.into(),
+            greeks.map(|g| g.delta).into(), greeks.map(|g| g.gamma).into(), greeks.map(|g| g.vega).into(),
+            greeks.map(|g| g.theta).into(), greeks.map(|g| g.rho).into()
+        ], &mut out);
+        // reader of the pipe has gone
+        if written.is_err() {
+            break;
+        }
+    }
+    out.flush().map_err(|e| e.to_string())
+}
+
+/// Evaluates records from stdin and writes result for each of them as soon as it is read,
+/// failed records are reported in the error column and don't stop the stream
+fn run_stream(matches: &ArgMatches, format: Format) -> Result<(), String> {
+    let is_json = matches.value_of("input") == Some("json");
+    let rate = float_value(matches, "rate");
+    let div_yield = float_value(matches, "div_yield");
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    let mut writer = RowWriter::new(format, &RESULT_COLUMNS);
+    let mut header: Option<Vec<String>> = None;
+
+    for (i, line) in stdin.lock().lines().enumerate() {
+        let line = line.map_err(|e| format!("can't read stdin: {}", e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = if is_json {
+            json_fields(&line)
+        } else if let Some(h) = header.as_ref() {
+            csv_fields(h, &line)
+        } else {
+            header = Some(line.split(',').map(|f| f.trim().trim_matches('"').to_string()).collect());
+            continue;
+        };
+        let written = writer.write(&result_row(i + 1, fields, rate, div_yield), &mut out);
+        // reader of the pipe has gone
+        if written.is_err() {
+            break;
+        }
+    }
+    out.flush().map_err(|e| e.to_string())
+}
+
+/// P&L and greeks of positions in each scenario of spot, volatility and time shifts grid
+fn run_scenario(matches: &ArgMatches) -> Result<Table, String> {
+    let positions = match matches.value_of("positions") {
+        Some(path) => read_positions(path, matches)?,
+        None => vec![OptionPosition {
+            is_call: matches.value_of("type") != Some("put"),
+            quantity: float_value(matches, "quantity"),
+            bs_params: bs_params(matches)
+        }]
+    };
+    let grid = ScenarioGrid {
+        spot_shifts: float_values(matches, "spot-shifts").unwrap(),
+        vol_shifts: float_values(matches, "vol-shifts").unwrap(),
+        time_shifts: float_values(matches, "time-shifts").unwrap()
+    };
+
+    let mut table = Table::new(&["spot_shift", "vol_shift", "time_shift", "pnl", "value", "delta", "gamma", "vega", "theta", "rho"]);
+    for cell in scenario_ladder(&positions, &grid)? {
+        let g = cell.greeks;
+        table.push(vec![
+            cell.spot_shift.into(), cell.vol_shift.into(), cell.time_shift.into(), cell.pnl.into(),
+            g.premium.into(), g.delta.into(), g.gamma.into(), g.vega.into(), g.theta.into(), g.rho.into()
+        ]);
+    }
+    Ok(table)
+}
+
+/// Option positions from CSV file with header, positions with premium instead of volatility use implied volatility
+fn read_positions(path: &str, matches: &ArgMatches) -> Result<Vec<OptionPosition>, String> {
+    let rate = matches.value_of("rate").map(|v| v.parse().unwrap()).unwrap_or(0.0);
+    let div_yield = float_value(matches, "div_yield");
+    let lines = read_lines(path)?;
+    let header: Vec<String> = lines.first().ok_or_else(|| format!("{} is empty", path))?
+        .split(',').map(|f| f.trim().to_string()).collect();
+
+    let mut positions = Vec::new();
+    for (i, line) in lines.iter().enumerate().skip(1) {
+        let position = csv_fields(&header, line).and_then(|fields| {
+            let record = StreamRecord::from_fields(&fields, rate, div_yield)?;
+            let (vol, _) = record.evaluate()?;
+            let quantity = fields.iter().find(|(k, _)| k == "quantity").map(|(_, v)| v.as_str()).unwrap_or("1");
+            let quantity: f64 = quantity.parse().map_err(|_| format!("quantity is not a number: '{}'", quantity))?;
+            Ok(OptionPosition { is_call: record.is_call, quantity, bs_params: BlackScholesParams { vol, ..record.bs_params } })
+        });
+        positions.push(position.map_err(|e| format!("line {}: {}", i + 1, e))?);
+    }
+    Ok(positions)
+}
@@ -0,0 +1,219 @@
+//! module with CLI results output as JSON (one object per row), CSV or aligned table.
+use std::fmt::Write as _;
+use std::io;
+use std::io::Write;
+use std::str::FromStr;
+
+/// Decimal places of numbers in aligned tables
+const TABLE_PRECISION: usize = 6;
+/// Minimal column width of streamed tables, which can't be aligned by their content
+const STREAM_COLUMN_WIDTH: usize = 12;
+
+/// Output format of results
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Json,
+    Csv,
+    Table
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "table" => Ok(Format::Table),
+            _ => Err(format!("unknown output format: '{}'", s))
+        }
+    }
+}
+
+/// Value of table cell
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Integer(i64),
+    Text(String),
+    Missing
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Value {
+        Value::Number(v)
+    }
+}
+
+impl From<usize> for Value {
+    fn from(v: usize) -> Value {
+        Value::Integer(v as i64)
+    }
+}
+
+impl From<Option<f64>> for Value {
+    fn from(v: Option<f64>) -> Value {
+        v.map(Value::Number).unwrap_or(Value::Missing)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Value {
+        Value::Text(v.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Value {
+        Value::Text(v)
+    }
+}
+
+/// Results with named columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>
+}
+
+impl Table {
+
+    pub fn new(columns: &[&str]) -> Table {
+        Table { columns: columns.iter().map(|c| c.to_string()).collect(), rows: Vec::new() }
+    }
+
+    /// Table with one row of named values
+    pub fn from_values(values: Vec<(String, Value)>) -> Table {
+        let (columns, row) = values.into_iter().unzip();
+        Table { columns, rows: vec![row] }
+    }
+
+    pub fn push(&mut self, row: Vec<Value>) {
+        self.rows.push(row);
+    }
+
+    pub fn write<W: Write>(&self, format: Format, out: &mut W) -> io::Result<()> {
+        match format {
+            Format::Json => {
+                for row in self.rows.iter() {
+                    writeln!(out, "{}", json_object(&self.columns, row))?;
+                }
+            },
+            Format::Csv => {
+                writeln!(out, "{}", csv_line(&self.columns.iter().map(|c| Value::Text(c.clone())).collect::<Vec<Value>>()))?;
+                for row in self.rows.iter() {
+                    writeln!(out, "{}", csv_line(row))?;
+                }
+            },
+            Format::Table => {
+                let cells: Vec<Vec<String>> = self.rows.iter().map(|r| r.iter().map(table_cell).collect()).collect();
+                let widths: Vec<usize> = self.columns.iter().enumerate()
+                    .map(|(j, c)| cells.iter().map(|r| r[j].len()).chain(std::iter::once(c.len())).max().unwrap_or(0))
+                    .collect();
+                // columns with numbers are right aligned
+                let numeric: Vec<bool> = (0..self.columns.len())
+                    .map(|j| self.rows.iter().any(|r| matches!(r[j], Value::Number(_) | Value::Integer(_))))
+                    .collect();
+                let align = |cell: &str, j: usize| if numeric[j] {format!("{:>w$}", cell, w = widths[j])} else {format!("{:<w$}", cell, w = widths[j])};
+                let header: Vec<String> = self.columns.iter().enumerate().map(|(j, c)| align(c, j)).collect();
+                writeln!(out, "{}", header.join("  ").trim_end())?;
+                for row in cells.iter() {
+                    let line: Vec<String> = row.iter().enumerate().map(|(j, cell)| align(cell, j)).collect();
+                    writeln!(out, "{}", line.join("  ").trim_end())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writer of rows one by one for streaming, table columns have fixed width
+pub struct RowWriter {
+    format: Format,
+    columns: Vec<String>,
+    header_written: bool
+}
+
+impl RowWriter {
+
+    pub fn new(format: Format, columns: &[&str]) -> RowWriter {
+        RowWriter { format, columns: columns.iter().map(|c| c.to_string()).collect(), header_written: false }
+    }
+
+    /// Writes row (with header before the first row) and flushes the output
+    pub fn write<W: Write>(&mut self, row: &[Value], out: &mut W) -> io::Result<()> {
+        let width = |c: &String| c.len().max(STREAM_COLUMN_WIDTH);
+        match self.format {
+            Format::Json => writeln!(out, "{}", json_object(&self.columns, row))?,
+            Format::Csv => {
+                if !self.header_written {
+                    writeln!(out, "{}", csv_line(&self.columns.iter().map(|c| Value::Text(c.clone())).collect::<Vec<Value>>()))?;
+                }
+                writeln!(out, "{}", csv_line(row))?;
+            },
+            Format::Table => {
+                if !self.header_written {
+                    let header: Vec<String> = self.columns.iter().map(|c| format!("{:>w$}", c, w = width(c))).collect();
+                    writeln!(out, "{}", header.join("  "))?;
+                }
+                let line: Vec<String> = row.iter().zip(self.columns.iter()).map(|(v, c)| format!("{:>w$}", table_cell(v), w = width(c))).collect();
+                writeln!(out, "{}", line.join("  ").trim_end())?;
+            }
+        }
+        self.header_written = true;
+        out.flush()
+    }
+}
+
+/// JSON object with columns as keys, missing and not finite numbers are `null`
+pub fn json_object(columns: &[String], row: &[Value]) -> String {
+    let fields: Vec<String> = columns.iter().zip(row.iter()).map(|(c, v)| {
+        let value = match v {
+            Value::Number(x) if x.is_finite() => x.to_string(),
+            Value::Integer(x) => x.to_string(),
+            Value::Text(s) => json_string(s),
+            _ => "null".to_string()
+        };
+        format!("{}:{}", json_string(c), value)
+    }).collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => { let _ = write!(escaped, "\\u{:04x}", c as u32); },
+            c => escaped.push(c)
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// CSV line, text with separators or quotes is quoted
+pub fn csv_line(row: &[Value]) -> String {
+    let fields: Vec<String> = row.iter().map(|v| match v {
+        Value::Number(x) => x.to_string(),
+        Value::Integer(x) => x.to_string(),
+        Value::Text(s) if s.contains([',', '"', '\n']) => format!("\"{}\"", s.replace('"', "\"\"")),
+        Value::Text(s) => s.clone(),
+        Value::Missing => String::new()
+    }).collect();
+    fields.join(",")
+}
+
+fn table_cell(value: &Value) -> String {
+    match value {
+        Value::Number(x) => format!("{:.p$}", x, p = TABLE_PRECISION),
+        Value::Integer(x) => x.to_string(),
+        Value::Text(s) => s.clone(),
+        Value::Missing => "-".to_string()
+    }
+}
@@ -0,0 +1,60 @@
+#![cfg(test)]
+use crate::output::*;
+
+fn table() -> Table {
+    let mut table = Table::new(&["symbol", "strike", "vol"]);
+    table.push(vec!["SPX".into(), 4800.0.into(), 0.1525.into()]);
+    table.push(vec!["say \"hi\", ok".into(), 95.5.into(), None.into()]);
+    table
+}
+
+fn written(format: Format) -> String {
+    let mut out = Vec::new();
+    table().write(format, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_json() {
+    assert_eq!(written(Format::Json),
+               "{\"symbol\":\"SPX\",\"strike\":4800,\"vol\":0.1525}\n\
+                {\"symbol\":\"say \\\"hi\\\", ok\",\"strike\":95.5,\"vol\":null}\n");
+    let columns = vec!["x".to_string()];
+    assert_eq!(json_object(&columns, &[f64::NAN.into()]), "{\"x\":null}");
+}
+
+#[test]
+fn test_csv() {
+    assert_eq!(written(Format::Csv), "symbol,strike,vol\nSPX,4800,0.1525\n\"say \"\"hi\"\", ok\",95.5,\n");
+}
+
+#[test]
+fn test_table() {
+    assert_eq!(written(Format::Table),
+               "symbol             strike       vol\n\
+                SPX           4800.000000  0.152500\n\
+                say \"hi\", ok    95.500000         -\n");
+    assert_eq!("table".parse::<Format>(), Ok(Format::Table));
+    assert!("xml".parse::<Format>().is_err());
+}
+
+#[test]
+fn test_row_writer() {
+    let rows = table().rows;
+    let mut csv = RowWriter::new(Format::Csv, &["symbol", "strike", "vol"]);
+    let mut out = Vec::new();
+    for row in rows.iter() {
+        csv.write(row, &mut out).unwrap();
+    }
+    assert_eq!(String::from_utf8(out).unwrap(), written(Format::Csv));
+
+    let mut json = RowWriter::new(Format::Json, &["symbol", "strike", "vol"]);
+    let mut out = Vec::new();
+    json.write(&rows[0], &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "{\"symbol\":\"SPX\",\"strike\":4800,\"vol\":0.1525}\n");
+
+    let mut table = RowWriter::new(Format::Table, &["symbol", "strike"]);
+    let mut out = Vec::new();
+    table.write(&rows[0][..2], &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "      symbol        strike\n         SPX   4800.000000\n");
+}
@@ -0,0 +1,220 @@
+//! module with streaming evaluation of contract records read line by line as NDJSON objects or CSV rows with header.
+//! Record fields are `price`, `strike`, `time_to_expiry`, `rate`, `div_yield`, `type` (call or put, call by default),
+//! `vol` or `premium` and optional `id`, which is copied to the result. Records with volatility are priced,
+//! records with premium are solved for implied volatility.
+use ivol::black_scholes::*;
+use ivol::chain::{parse_option_type, within_bounds};
+use crate::output::Value;
+
+/// Columns of result rows
+pub const RESULT_COLUMNS: [&str; 11] = ["line", "id", "type", "premium", "vol", "delta", "gamma", "vega", "theta", "rho", "error"];
+
+/// Named fields of one input record
+pub type Fields = Vec<(String, String)>;
+
+/// Contract record of the stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamRecord {
+    pub id: Option<String>,
+    pub is_call: bool,
+    pub bs_params: BlackScholesParams,
+    /// market premium to solve implied volatility for, if volatility is not given
+    pub premium: Option<f64>
+}
+
+impl StreamRecord {
+
+    /// Record from named fields, `rate` and `div_yield` are used when record doesn't have them
+    pub fn from_fields(fields: &[(String, String)], rate: f64, div_yield: f64) -> Result<StreamRecord, String> {
+        let text = |name: &str| fields.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str()).filter(|v| !v.is_empty());
+        let number = |name: &str| -> Result<Option<f64>, String> {
+            text(name).map(|v| v.parse::<f64>().ok().filter(|x| x.is_finite()).ok_or_else(|| format!("{} is not a number: '{}'", name, v))).transpose()
+        };
+        let required = |name: &str| number(name)?.ok_or_else(|| format!("{} is missing", name));
+
+        let vol = number("vol")?;
+        let premium = number("premium")?;
+        if vol.is_none() && premium.is_none() {
+            return Err("either vol or premium is needed".to_string());
+        }
+        Ok(StreamRecord {
+            id: text("id").map(|v| v.to_string()),
+            is_call: text("type").map(parse_option_type).transpose()?.unwrap_or(true),
+            bs_params: BlackScholesParams {
+                price: required("price")?,
+                strike: required("strike")?,
+                rate: number("rate")?.unwrap_or(rate),
+                div_yield: number("div_yield")?.unwrap_or(div_yield),
+                vol: vol.unwrap_or(0.0),
+                time_to_expiry: required("time_to_expiry")?
+            },
+            premium: if vol.is_some() {None} else {premium}
+        })
+    }
+
+    /// Volatility (given or implied) with premium and greeks
+    pub fn evaluate(&self) -> Result<(f64, Greeks), String> {
+        let p = &self.bs_params;
+        if [p.price, p.strike, p.rate, p.div_yield, p.vol, p.time_to_expiry].iter().chain(self.premium.iter()).any(|v| !v.is_finite()) {
+            return Err("all numbers must be finite".to_string());
+        }
+        if p.price <= 0.0 || p.strike <= 0.0 || p.time_to_expiry <= 0.0 {
+            return Err("price, strike and time to expiry must be positive".to_string());
+        }
+        if self.premium.is_none() && p.vol <= 0.0 {
+            return Err("vol must be positive".to_string());
+        }
+        let vol = match self.premium {
+            Some(premium) => {
+                if !within_bounds(self.is_call, premium, p) {
+                    return Err("premium is outside of no-arbitrage bounds".to_string());
+                }
+                let vol = if self.is_call {call_impl_vol(&premium, p)} else {put_impl_vol(&premium, p)};
+                vol.ok().filter(|v| v.is_finite() && *v > 0.0).ok_or_else(|| "implied volatility did not converge".to_string())?
+            },
+            None => p.vol
+        };
+        Ok((vol, option_greeks(self.is_call, &BlackScholesParams { vol, ..*p })))
+    }
+}
+
+/// Result row for record parsed from input line `line`, with error message if it failed.
+/// Id of the record is reported even if other fields are invalid.
+pub fn result_row(line: usize, fields: Result<Fields, String>, rate: f64, div_yield: f64) -> Vec<Value> {
+    let fields = match fields {
+        Ok(f) => f,
+        Err(e) => return error_row(line, Value::Missing, e)
+    };
+    let id = fields.iter().find(|(k, v)| k == "id" && !v.is_empty()).map(|(_, v)| Value::Text(v.clone())).unwrap_or(Value::Missing);
+    match StreamRecord::from_fields(&fields, rate, div_yield).and_then(|r| r.evaluate().map(|e| (r.is_call, e))) {
+        Ok((is_call, (vol, g))) => vec![
+            line.into(), id, if is_call {"call"} else {"put"}.into(),
+            g.premium.into(), vol.into(), g.delta.into(), g.gamma.into(), g.vega.into(), g.theta.into(), g.rho.into(), Value::Missing
+        ],
+        Err(e) => error_row(line, id, e)
+    }
+}
+
+fn error_row(line: usize, id: Value, error: String) -> Vec<Value> {
+    let mut row = vec![Value::Missing; RESULT_COLUMNS.len()];
+    row[0] = line.into();
+    row[1] = id;
+    row[RESULT_COLUMNS.len() - 1] = Value::Text(error);
+    row
+}
+
+/// Fields of CSV row with names from header
+pub fn csv_fields(header: &[String], line: &str) -> Result<Fields, String> {
+    let values: Vec<&str> = line.split(',').map(|f| f.trim().trim_matches('"')).collect();
+    if values.len() != header.len() {
+        return Err(format!("expected {} fields, found {}", header.len(), values.len()));
+    }
+    Ok(header.iter().cloned().zip(values.into_iter().map(|v| v.to_string())).collect())
+}
+
+/// Fields of flat JSON object, numbers and booleans are kept as text and `null` as empty value
+pub fn json_fields(line: &str) -> Result<Fields, String> {
+    let mut parser = JsonParser { chars: line.trim().chars().collect(), pos: 0 };
+    parser.expect('{')?;
+    let mut fields = Vec::new();
+    if parser.peek() == Some('}') {
+        parser.pos += 1;
+    } else {
+        loop {
+            let key = parser.string()?;
+            parser.expect(':')?;
+            fields.push((key, parser.value()?));
+            match parser.next() {
+                Some(',') => continue,
+                Some('}') => break,
+                _ => return Err("expected ',' or '}' in JSON object".to_string())
+            }
+        }
+    }
+    if parser.peek().is_some() {
+        return Err("unexpected characters after JSON object".to_string());
+    }
+    Ok(fields)
+}
+
+struct JsonParser {
+    chars: Vec<char>,
+    pos: usize
+}
+
+impl JsonParser {
+
+    /// Next character after whitespace
+    fn peek(&mut self) -> Option<char> {
+        while self.pos < self.chars.len() && self.chars[self.pos].is_whitespace() {
+            self.pos += 1;
+        }
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += 1;
+        c
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(format!("expected '{}' in JSON object", expected))
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            let c = *self.chars.get(self.pos).ok_or_else(|| "unterminated JSON string".to_string())?;
+            self.pos += 1;
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let e = *self.chars.get(self.pos).ok_or_else(|| "unterminated JSON string".to_string())?;
+                    self.pos += 1;
+                    match e {
+                        'n' => s.push('\n'),
+                        't' => s.push('\t'),
+                        'r' => s.push('\r'),
+                        'b' => s.push('\u{8}'),
+                        'f' => s.push('\u{c}'),
+                        'u' => {
+                            let hex: String = self.chars.iter().skip(self.pos).take(4).collect();
+                            let code = u32::from_str_radix(&hex, 16).map_err(|_| "invalid JSON unicode escape".to_string())?;
+                            s.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                            self.pos += 4;
+                        },
+                        c => s.push(c)
+                    }
+                },
+                c => s.push(c)
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<String, String> {
+        match self.peek() {
+            Some('"') => self.string(),
+            Some('{') | Some('[') => Err("nested JSON values are not supported".to_string()),
+            Some(_) => {
+                let start = self.pos;
+                while self.pos < self.chars.len() && !matches!(self.chars[self.pos], ',' | '}') && !self.chars[self.pos].is_whitespace() {
+                    self.pos += 1;
+                }
+                let token: String = self.chars[start..self.pos].iter().collect();
+                match token.as_str() {
+                    "null" => Ok(String::new()),
+                    "true" | "false" => Ok(token),
+                    // JSON has no NaN and infinity, which are valid for Rust parser
+                    _ if token.parse::<f64>().is_ok_and(|v| v.is_finite()) => Ok(token),
+                    _ => Err(format!("invalid JSON value: '{}'", token))
+                }
+            },
+            None => Err("unexpected end of JSON object".to_string())
+        }
+    }
+}
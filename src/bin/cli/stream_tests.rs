@@ -0,0 +1,92 @@
+#![cfg(test)]
+use ivol::black_scholes::*;
+use crate::output::Value;
+use crate::stream::*;
+
+const EPS: f64 = 0.0001;
+
+fn fields(pairs: &[(&str, &str)]) -> Fields {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+#[test]
+fn test_json_fields() {
+    let parsed = json_fields(r#" {"id": "a \"1\"", "price":100, "strike" : 1.05e2, "vol": null, "flag": true} "#).unwrap();
+    assert_eq!(parsed, fields(&[("id", "a \"1\""), ("price", "100"), ("strike", "1.05e2"), ("vol", ""), ("flag", "true")]));
+    assert_eq!(json_fields("{}").unwrap(), fields(&[]));
+    assert_eq!(json_fields(r#"{"s":"é\n"}"#).unwrap(), fields(&[("s", "é\n")]));
+
+    assert!(json_fields(r#"{"price": 100"#).is_err());
+    assert!(json_fields(r#"{"price": abc}"#).is_err());
+    assert!(json_fields(r#"{"legs": [1, 2]}"#).is_err());
+    assert!(json_fields(r#"{"price": 1} x"#).is_err());
+    assert!(json_fields("price,strike").is_err());
+    assert!(json_fields(r#"{"price": NaN}"#).is_err());
+    assert!(json_fields(r#"{"price": inf}"#).is_err());
+    assert!(json_fields(r#"{"price": 1e400}"#).is_err());
+}
+
+#[test]
+fn test_csv_fields() {
+    let header = vec!["price".to_string(), "strike".to_string()];
+    assert_eq!(csv_fields(&header, "100, \"95\"").unwrap(), fields(&[("price", "100"), ("strike", "95")]));
+    assert!(csv_fields(&header, "100").is_err());
+}
+
+#[test]
+fn test_records() {
+    let p = BlackScholesParams { price: 100.0, strike: 95.0, rate: 0.05, div_yield: 0.02, vol: 0.25, time_to_expiry: 0.5 };
+    let priced = StreamRecord::from_fields(&fields(&[("price", "100"), ("strike", "95"), ("time_to_expiry", "0.5"), ("vol", "0.25"), ("type", "P")]), 0.05, 0.02).unwrap();
+    assert_eq!(priced.bs_params, p);
+    let (vol, greeks) = priced.evaluate().unwrap();
+    assert_eq!(vol, 0.25);
+    assert_eq!(greeks, option_greeks(false, &p));
+
+    // implied volatility from premium, with record's own rate
+    let premium = call_premium(&BlackScholesParams { rate: 0.03, ..p }).to_string();
+    let quote = fields(&[("id", "x1"), ("price", "100"), ("strike", "95"), ("time_to_expiry", "0.5"), ("rate", "0.03"), ("premium", &premium)]);
+    let solved = StreamRecord::from_fields(&quote, 0.05, 0.02).unwrap();
+    assert!(solved.is_call);
+    assert!((solved.evaluate().unwrap().0 - 0.25).abs() < EPS);
+    let row = result_row(7, Ok(quote.clone()), 0.05, 0.02);
+    assert_eq!((&row[0], &row[1], &row[2]), (&Value::Integer(7), &Value::Text("x1".to_string()), &Value::Text("call".to_string())));
+    assert!(matches!(row[4], Value::Number(v) if (v - 0.25).abs() < EPS));
+    assert_eq!(row[10], Value::Missing);
+
+    assert!(StreamRecord::from_fields(&fields(&[("price", "100"), ("strike", "95"), ("time_to_expiry", "0.5")]), 0.05, 0.0).is_err());
+    assert!(StreamRecord::from_fields(&fields(&[("price", "x"), ("strike", "95"), ("time_to_expiry", "0.5"), ("vol", "0.2")]), 0.05, 0.0).is_err());
+
+    // errors are reported in the row
+    let row = result_row(3, Err("expected '{' in JSON object".to_string()), 0.05, 0.02);
+    assert_eq!(row.len(), RESULT_COLUMNS.len());
+    assert_eq!(row[10], Value::Text("expected '{' in JSON object".to_string()));
+    let unsolvable = StreamRecord { premium: Some(200.0), ..priced };
+    assert_eq!(unsolvable.evaluate(), Err("premium is outside of no-arbitrage bounds".to_string()));
+    let row = result_row(4, Ok(fields(&[("id", "x2"), ("price", "100"), ("vol", "0.2")])), 0.05, 0.02);
+    assert_eq!((&row[1], &row[10]), (&Value::Text("x2".to_string()), &Value::Text("strike is missing".to_string())));
+}
+
+
+#[test]
+fn test_invalid_numbers_are_record_errors() {
+    let header: Vec<String> = ["id", "price", "strike", "time_to_expiry", "vol"].iter().map(|h| h.to_string()).collect();
+    let lines = ["a,100,95,0.5,0", "b,100,95,0.5,-0.2", "c,NaN,95,0.5,0.2", "d,100,inf,0.5,0.2", "e,100,95,0.5,0.2"];
+    let rows: Vec<Vec<Value>> = lines.iter().enumerate().map(|(i, line)| result_row(i + 2, csv_fields(&header, line), 0.05, 0.0)).collect();
+    for row in rows[..4].iter() {
+        assert!(matches!(row[10], Value::Text(_)), "{:?}", row);
+        assert_eq!(row[3], Value::Missing);
+    }
+    assert_eq!(rows[0][10], Value::Text("vol must be positive".to_string()));
+    assert_eq!(rows[1][10], Value::Text("vol must be positive".to_string()));
+    assert_eq!(rows[2][10], Value::Text("price is not a number: 'NaN'".to_string()));
+
+    // the stream goes on after failed records
+    let p = BlackScholesParams { price: 100.0, strike: 95.0, rate: 0.05, div_yield: 0.0, vol: 0.2, time_to_expiry: 0.5 };
+    assert_eq!((&rows[4][1], &rows[4][10]), (&Value::Text("e".to_string()), &Value::Missing));
+    assert_eq!(rows[4][3], Value::Number(call_premium(&p)));
+
+    let json = result_row(1, json_fields(r#"{"price": 100, "strike": 95, "time_to_expiry": 0.5, "vol": 0}"#), 0.05, 0.0);
+    assert_eq!(json[10], Value::Text("vol must be positive".to_string()));
+    let record = StreamRecord { id: None, is_call: true, bs_params: BlackScholesParams { rate: f64::NAN, ..p }, premium: None };
+    assert_eq!(record.evaluate(), Err("all numbers must be finite".to_string()));
+}
@@ -9,7 +9,7 @@ const EPS : f64 = 0.0000001;
 const ITER: i32 = 60000;
 
 /// Parameters of BlackScholes
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BlackScholesParams {
     /// spot price of option underlying asset
     pub price: f64,
@@ -74,6 +74,62 @@ pub fn simulate_put<F>(source: &BlackScholesParams, bump: F) -> f64
     put_prem_bumped - put_prem
 }
 
+/// Option premium with its sensitivities, scaled the same way as Black/Scholes greeks of this module
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Greeks {
+    pub premium: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    /// premium change for 1% volatility change
+    pub vega: f64,
+    /// premium change per year
+    pub theta: f64,
+    /// premium change for 1% rate change
+    pub rho: f64
+}
+
+/// Black/Scholes premium and greeks of call or put option
+pub fn option_greeks(is_call: bool, bs_params: &BlackScholesParams) -> Greeks {
+    Greeks {
+        premium: generic_black_scholes(is_call, bs_params),
+        delta: generic_delta(is_call, bs_params),
+        gamma: gamma(bs_params),
+        vega: vega(bs_params),
+        theta: generic_theta(is_call, bs_params),
+        rho: generic_rho(is_call, bs_params)
+    }
+}
+
+/// Premium and greeks of arbitrary pricing function, calculated with central differences of bumped contracts.
+/// Theta is calculated by bumping `time_to_expiry` only.
+/// # Example
+/// ```
+/// use ivol::black_scholes::*;
+/// let bs_params = BlackScholesParams{price: 345.0, strike: 330.0, div_yield: 0.06, rate: 0.025, vol: 0.34, time_to_expiry: 1.0};
+/// let greeks = numerical_greeks(&bs_params, call_premium);
+/// assert!((greeks.delta - call_delta(&bs_params)).abs() < 0.0001);
+/// assert!((greeks.vega - vega(&bs_params)).abs() < 0.0001);
+/// ```
+pub fn numerical_greeks<F>(bs_params: &BlackScholesParams, pricer: F) -> Greeks
+    where F: Fn(&BlackScholesParams) -> f64 {
+    let premium = pricer(bs_params);
+    let central = |bump: &dyn Fn(f64) -> BlackScholesParams, h: f64| (pricer(&bump(h)) - pricer(&bump(-h))) / (2.0 * h);
+
+    let ds = 0.0001 * bs_params.price;
+    let up = pricer(&BlackScholesParams { price: bs_params.price + ds, ..*bs_params });
+    let down = pricer(&BlackScholesParams { price: bs_params.price - ds, ..*bs_params });
+    let dt = (0.5 * bs_params.time_to_expiry).min(0.00001);
+
+    Greeks {
+        premium,
+        delta: (up - down) / (2.0 * ds),
+        gamma: (up - 2.0 * premium + down) / (ds * ds),
+        vega: 0.01 * central(&|h| BlackScholesParams { vol: bs_params.vol + h, ..*bs_params }, 0.00001),
+        theta: -central(&|h| BlackScholesParams { time_to_expiry: bs_params.time_to_expiry + h, ..*bs_params }, dt),
+        rho: 0.01 * central(&|h| BlackScholesParams { rate: bs_params.rate + h, ..*bs_params }, 0.00001)
+    }
+}
+
 
 /// Delta sensitivity for call options
 pub fn call_delta(bs_params: &BlackScholesParams) -> f64 {
@@ -128,6 +184,21 @@ pub fn vega(bs_params: &BlackScholesParams) -> f64 {
     0.01 * dtv_dvol(bs_params)
 }
 
+/// Vanna sensitivity for call/put options: delta change for 1% volatility change
+pub fn vanna(bs_params: &BlackScholesParams) -> f64 {
+    let n: Gaussian = Gaussian::standard();
+    let d1 = d1(bs_params);
+    let d2 = d2(bs_params);
+    -0.01 * (-bs_params.div_yield * bs_params.time_to_expiry).exp() * n.pdf(&d1) * d2 / bs_params.vol
+}
+
+/// Volga (vomma) sensitivity for call/put options: vega change for 1% volatility change
+pub fn volga(bs_params: &BlackScholesParams) -> f64 {
+    let d1 = d1(bs_params);
+    let d2 = d2(bs_params);
+    0.0001 * dtv_dvol(bs_params) * d1 * d2 / bs_params.vol
+}
+
 /// Calculates implied volatility from call market price and other option parameters
 pub fn call_impl_vol(call_market_price: &f64, bs_params: &BlackScholesParams) -> Result<f64, f64> {
     let func = |v: f64| call_premium(&BlackScholesParams{vol: v, ..*bs_params}) - *call_market_price;
@@ -242,16 +313,12 @@ fn generic_delta(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
 #[inline]
 pub fn dtv_dvol(bs_params: &BlackScholesParams) -> f64 {
     let n: Gaussian = Gaussian::standard();
-    let d1 = d1(bs_params);
-    if !d1.is_normal() {
-        println!("Not normal d1 {:?}", bs_params);
-    }
-    bs_params.price * (-bs_params.div_yield * bs_params.time_to_expiry).exp() * n.pdf(&d1) * bs_params.time_to_expiry.sqrt()
+    bs_params.price * (-bs_params.div_yield * bs_params.time_to_expiry).exp() * n.pdf(&d1(bs_params)) * bs_params.time_to_expiry.sqrt()
 }
 
 /// Generic Black/Scholes calculation for both call and put options
 #[inline]
-fn generic_black_scholes(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+pub(crate) fn generic_black_scholes(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
     let sign = if is_call {1.0} else {-1.0};
     let n: Gaussian = Gaussian::standard();
     let d1 = sign * d1(bs_params);
@@ -264,7 +331,7 @@ fn generic_black_scholes(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
 
 /// D1 sub-formula of Black/Scholes
 #[inline]
-fn d1(bs_params: &BlackScholesParams) -> f64 {
+pub(crate) fn d1(bs_params: &BlackScholesParams) -> f64 {
     let mut d : f64 = bs_params.price / bs_params.strike;
     d = d.ln() + (bs_params.rate - bs_params.div_yield + bs_params.vol * bs_params.vol / 2.0) * bs_params.time_to_expiry;
     d / (bs_params.vol * bs_params.time_to_expiry.sqrt())
@@ -272,6 +339,6 @@ fn d1(bs_params: &BlackScholesParams) -> f64 {
 
 /// D2 sub-formula of Black/Scholes
 #[inline]
-fn d2(bs_params: &BlackScholesParams) -> f64 {
+pub(crate) fn d2(bs_params: &BlackScholesParams) -> f64 {
     d1(bs_params) - bs_params.vol * bs_params.time_to_expiry.sqrt()
 }
\ No newline at end of file
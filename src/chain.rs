@@ -0,0 +1,134 @@
+//! module with option chain quotes: parsing of CSV records `symbol,expiry,strike,type,bid,ask,last,underlying price`
+//! and implied volatility of bid, mid and ask premiums with greeks for each quote.
+//! Expiry is either a date `YYYY-MM-DD` or time to expiry in years.
+use crate::black_scholes::*;
+
+/// Days in year for time to expiry calculation (ACT/365)
+const DAYS_IN_YEAR: f64 = 365.0;
+
+/// One quote of option chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainRecord {
+    pub symbol: String,
+    pub expiry: String,
+    pub strike: f64,
+    pub is_call: bool,
+    pub bid: f64,
+    pub ask: f64,
+    pub last: f64,
+    pub underlying_price: f64
+}
+
+/// Implied volatilities of the quote, `None` when premium is missing or can't be matched by any volatility.
+/// Greeks are calculated with mid volatility.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChainResult {
+    pub bid_vol: Option<f64>,
+    pub mid_vol: Option<f64>,
+    pub ask_vol: Option<f64>,
+    pub greeks: Option<Greeks>
+}
+
+impl ChainRecord {
+
+    /// Parses comma separated record, empty premiums are treated as zero
+    pub fn from_csv(line: &str) -> Result<ChainRecord, String> {
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim().trim_matches('"')).collect();
+        if fields.len() != 8 {
+            return Err(format!("expected 8 fields, found {}", fields.len()));
+        }
+        let number = |i: usize, name: &str| -> Result<f64, String> {
+            // bid, ask and last may be missing
+            if fields[i].is_empty() && (4..=6).contains(&i) {
+                return Ok(0.0);
+            }
+            fields[i].parse::<f64>().map_err(|_| format!("{} is not a number: '{}'", name, fields[i]))
+        };
+        Ok(ChainRecord {
+            symbol: fields[0].to_string(),
+            expiry: fields[1].to_string(),
+            strike: number(2, "strike")?,
+            is_call: parse_option_type(fields[3])?,
+            bid: number(4, "bid")?,
+            ask: number(5, "ask")?,
+            last: number(6, "last")?,
+            underlying_price: number(7, "underlying price")?
+        })
+    }
+
+    /// Time to expiry in years from `valuation_date` (`YYYY-MM-DD`), which is needed only for expiry dates
+    pub fn time_to_expiry(&self, valuation_date: Option<&str>) -> Result<f64, String> {
+        if let Ok(t) = self.expiry.parse::<f64>() {
+            return Ok(t);
+        }
+        let date = valuation_date.ok_or_else(|| "valuation date is needed for expiry dates".to_string())?;
+        Ok((days_since_epoch(&self.expiry)? - days_since_epoch(date)?) as f64 / DAYS_IN_YEAR)
+    }
+
+    /// Implied volatilities and greeks of the quote with given market parameters
+    pub fn evaluate(&self, time_to_expiry: f64, rate: f64, div_yield: f64) -> ChainResult {
+        let bs_params = BlackScholesParams {
+            price: self.underlying_price,
+            strike: self.strike,
+            rate,
+            div_yield,
+            vol: 0.0,
+            time_to_expiry
+        };
+        let valid = time_to_expiry > 0.0 && self.strike > 0.0 && self.underlying_price > 0.0;
+        let impl_vol = |premium: f64| {
+            if !valid || !within_bounds(self.is_call, premium, &bs_params) {
+                return None;
+            }
+            let vol = if self.is_call {call_impl_vol(&premium, &bs_params)} else {put_impl_vol(&premium, &bs_params)};
+            vol.ok().filter(|v| v.is_finite() && *v > 0.0)
+        };
+
+        let mid_vol = if self.bid > 0.0 && self.ask > 0.0 {impl_vol((self.bid + self.ask) / 2.0)} else {None};
+        let greeks = mid_vol.map(|vol| option_greeks(self.is_call, &BlackScholesParams { vol, ..bs_params }));
+        ChainResult { bid_vol: impl_vol(self.bid), mid_vol, ask_vol: impl_vol(self.ask), greeks }
+    }
+}
+
+/// Checks that premium is strictly between no-arbitrage bounds, otherwise no volatility can match it
+pub fn within_bounds(is_call: bool, premium: f64, bs_params: &BlackScholesParams) -> bool {
+    let dprice = bs_params.price * (-bs_params.div_yield * bs_params.time_to_expiry).exp();
+    let dstrike = bs_params.strike * (-bs_params.rate * bs_params.time_to_expiry).exp();
+    let (intrinsic, upper) = if is_call {(dprice - dstrike, dprice)} else {(dstrike - dprice, dstrike)};
+    premium > intrinsic.max(0.0) && premium < upper
+}
+
+/// Parses option type: `C`, `call`, `P` or `put` in any case
+pub fn parse_option_type(value: &str) -> Result<bool, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "c" | "call" => Ok(true),
+        "p" | "put" => Ok(false),
+        _ => Err(format!("unknown option type: '{}'", value))
+    }
+}
+
+/// Number of days from 1970-01-01 to the date in `YYYY-MM-DD` format
+pub fn days_since_epoch(date: &str) -> Result<i64, String> {
+    let error = || format!("date must be in YYYY-MM-DD format: '{}'", date);
+    let parts: Vec<i64> = date.split('-').map(|p| p.parse::<i64>()).collect::<Result<_, _>>().map_err(|_| error())?;
+    if parts.len() != 3 || !(1..=12).contains(&parts[1]) {
+        return Err(error());
+    }
+    let leap = parts[0] % 4 == 0 && (parts[0] % 100 != 0 || parts[0] % 400 == 0);
+    let month_days = match parts[1] {
+        2 => if leap {29} else {28},
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    };
+    if !(1..=month_days).contains(&parts[2]) {
+        return Err(format!("day {} is not in month {}: '{}'", parts[2], parts[1], date));
+    }
+
+    // days from civil (Hinnant)
+    let (y, m, d) = (if parts[1] <= 2 {parts[0] - 1} else {parts[0]}, parts[1], parts[2]);
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (if m > 2 {m - 3} else {m + 9}) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Ok(era * 146097 + doe - 719468)
+}
@@ -0,0 +1,72 @@
+#![cfg(test)]
+use crate::black_scholes::*;
+use crate::chain::*;
+
+const EPS: f64 = 0.0001;
+
+#[test]
+fn test_parse_record() {
+    let record = ChainRecord::from_csv("SPX, 2024-03-15, 4800, C, 61.2, 62.4, 61.9, 4783.5").unwrap();
+    assert_eq!(record.symbol, "SPX");
+    assert_eq!(record.expiry, "2024-03-15");
+    assert!(record.is_call);
+    assert_eq!((record.strike, record.bid, record.ask, record.last, record.underlying_price), (4800.0, 61.2, 62.4, 61.9, 4783.5));
+
+    let put = ChainRecord::from_csv("\"AAPL\",0.25,180,put,,4.1,,185").unwrap();
+    assert!(!put.is_call);
+    assert_eq!((put.bid, put.last), (0.0, 0.0));
+
+    assert!(ChainRecord::from_csv("symbol,expiry,strike,type,bid,ask,last,underlying").is_err());
+    assert!(ChainRecord::from_csv("SPX,0.25,4800,X,1,2,1,4800").is_err());
+    assert!(ChainRecord::from_csv("SPX,0.25,4800,C,1,2").is_err());
+}
+
+#[test]
+fn test_time_to_expiry() {
+    assert_eq!(days_since_epoch("1970-01-01").unwrap(), 0);
+    assert_eq!(days_since_epoch("2000-03-01").unwrap(), 11017);
+    assert_eq!(days_since_epoch("2024-03-01").unwrap() - days_since_epoch("2024-02-28").unwrap(), 2);
+    assert!(days_since_epoch("2024-13-01").is_err());
+    // impossible calendar dates must not roll into the next month
+    assert!(days_since_epoch("2024-02-29").is_ok());
+    assert!(days_since_epoch("2000-02-29").is_ok());
+    assert!(days_since_epoch("2024-02-30").is_err());
+    assert!(days_since_epoch("2024-02-31").is_err());
+    assert!(days_since_epoch("2023-02-29").is_err());
+    assert!(days_since_epoch("1900-02-29").is_err());
+    assert!(days_since_epoch("2024-04-31").is_err());
+    assert!(days_since_epoch("2024-01-00").is_err());
+
+    let record = ChainRecord::from_csv("SPX,2024-12-20,4800,C,1,2,1,4800").unwrap();
+    assert!((record.time_to_expiry(Some("2024-06-20")).unwrap() - 183.0 / 365.0).abs() < 1e-12);
+    assert!(record.time_to_expiry(None).is_err());
+    let record = ChainRecord { expiry: "0.5".to_string(), ..record };
+    assert_eq!(record.time_to_expiry(None).unwrap(), 0.5);
+}
+
+#[test]
+fn test_evaluate() {
+    let p = BlackScholesParams { price: 100.0, strike: 95.0, rate: 0.03, div_yield: 0.01, vol: 0.0, time_to_expiry: 0.5 };
+    let premium = |vol: f64| put_premium(&BlackScholesParams { vol, ..p });
+    let record = ChainRecord {
+        symbol: "XYZ".to_string(), expiry: "0.5".to_string(), strike: p.strike, is_call: false,
+        bid: premium(0.2), ask: premium(0.22), last: 0.0, underlying_price: p.price
+    };
+    let result = record.evaluate(0.5, p.rate, p.div_yield);
+    assert!((result.bid_vol.unwrap() - 0.2).abs() < EPS);
+    assert!((result.ask_vol.unwrap() - 0.22).abs() < EPS);
+    let mid_vol = result.mid_vol.unwrap();
+    assert!(mid_vol > 0.2 && mid_vol < 0.22);
+    let greeks = result.greeks.unwrap();
+    assert!((greeks.premium - (record.bid + record.ask) / 2.0).abs() < EPS);
+    assert!((greeks.delta - put_delta(&BlackScholesParams { vol: mid_vol, ..p })).abs() < EPS);
+
+    // missing bid gives no bid and mid volatility
+    let result = ChainRecord { bid: 0.0, ..record.clone() }.evaluate(0.5, p.rate, p.div_yield);
+    assert_eq!((result.bid_vol, result.mid_vol, result.greeks), (None, None, None));
+    assert!(result.ask_vol.is_some());
+
+    // premiums outside of no-arbitrage bounds have no volatility
+    let result = ChainRecord { bid: 0.5 * (p.strike - p.price), ask: 120.0, ..record }.evaluate(0.5, p.rate, p.div_yield);
+    assert_eq!((result.bid_vol, result.ask_vol), (None, None));
+}
@@ -0,0 +1,155 @@
+//! module with compound options (Geske) and chooser options (Rubinstein).
+use rv::prelude::*;
+use crate::black_scholes::*;
+use crate::math::{bisection, bivariate_normal_cdf};
+
+/// Relative precision of critical underlying price
+const EPS: f64 = 1e-12;
+/// Maximum number of bracket doublings in search of critical underlying price
+const BRACKET_STEPS: usize = 60;
+
+/// Compound option terms. Underlying option is described by [`BlackScholesParams`] (its strike and expiry).
+#[derive(Debug, Clone, Copy)]
+pub struct CompoundParams {
+    /// premium paid for the underlying option when compound option is exercised
+    pub strike: f64,
+    /// time to expiry of the compound option in years, must be less than expiry of underlying option
+    pub time_to_expiry: f64
+}
+
+/// Complex chooser terms: at choice time holder chooses between call and put with different strikes and expiries
+#[derive(Debug, Clone, Copy)]
+pub struct ComplexChooserParams {
+    /// time to the choice date in years
+    pub choice_time: f64,
+    pub call_strike: f64,
+    pub call_expiry: f64,
+    pub put_strike: f64,
+    pub put_expiry: f64
+}
+
+/// Compound option premium (Geske)
+/// * is_call - compound option is call (right to buy the underlying option) or put (right to sell it)
+/// * is_underlying_call - underlying option is call or put
+pub fn compound_premium(is_call: bool, is_underlying_call: bool, bs_params: &BlackScholesParams, compound: &CompoundParams) -> f64 {
+    let eta = if is_call {1.0} else {-1.0};
+    let phi = if is_underlying_call {1.0} else {-1.0};
+    let n: Gaussian = Gaussian::standard();
+    let t1 = compound.time_to_expiry;
+    let t2 = bs_params.time_to_expiry;
+
+    // underlying price at which the underlying option is worth the compound strike
+    let remaining = |s: f64| BlackScholesParams { price: s, time_to_expiry: t2 - t1, ..*bs_params };
+    let func = |s: f64| generic_black_scholes(is_underlying_call, &remaining(s)) - compound.strike;
+    let guess = (bs_params.strike + phi * compound.strike).max(0.01 * bs_params.strike);
+    let critical = critical_price(func, guess);
+
+    let y1 = d1(&BlackScholesParams { strike: critical, time_to_expiry: t1, ..*bs_params });
+    let y2 = y1 - bs_params.vol * t1.sqrt();
+    let z1 = d1(bs_params);
+    let z2 = d2(bs_params);
+    let rho = (t1 / t2).sqrt();
+
+    let dprice = bs_params.price * (-bs_params.div_yield * t2).exp();
+    let dstrike = bs_params.strike * (-bs_params.rate * t2).exp();
+    eta * phi * (dprice * bivariate_normal_cdf(phi * z1, eta * phi * y1, eta * rho)
+        - dstrike * bivariate_normal_cdf(phi * z2, eta * phi * y2, eta * rho))
+        - eta * compound.strike * (-bs_params.rate * t1).exp() * n.cdf(&(eta * phi * y2))
+}
+
+/// Compound option premium with greeks, theta accounts for both compound and underlying option expiries
+pub fn compound_greeks(is_call: bool, is_underlying_call: bool, bs_params: &BlackScholesParams, compound: &CompoundParams) -> Greeks {
+    let mut greeks = numerical_greeks(bs_params, |p| compound_premium(is_call, is_underlying_call, p, compound));
+    greeks.theta = calendar_theta(bs_params, compound.time_to_expiry, |p, shift| {
+        compound_premium(is_call, is_underlying_call, p, &CompoundParams { time_to_expiry: compound.time_to_expiry + shift, ..*compound })
+    });
+    greeks
+}
+
+/// Simple chooser premium: at `choice_time` holder chooses between call and put with strike and expiry of `bs_params`
+pub fn simple_chooser_premium(bs_params: &BlackScholesParams, choice_time: f64) -> f64 {
+    let n: Gaussian = Gaussian::standard();
+    let t = bs_params.time_to_expiry;
+    let carry = bs_params.rate - bs_params.div_yield;
+    let vt1 = bs_params.vol * choice_time.sqrt();
+    let y = ((bs_params.price / bs_params.strike).ln() + carry * t + bs_params.vol * bs_params.vol * choice_time / 2.0) / vt1;
+
+    // call plus put with expiry at choice time on strike discounted with carry
+    let dprice = bs_params.price * (-bs_params.div_yield * t).exp();
+    let dstrike = bs_params.strike * (-bs_params.rate * t).exp();
+    call_premium(bs_params) + dstrike * n.cdf(&(-y + vt1)) - dprice * n.cdf(&-y)
+}
+
+/// Simple chooser premium with greeks, theta accounts for both choice time and option expiry
+pub fn simple_chooser_greeks(bs_params: &BlackScholesParams, choice_time: f64) -> Greeks {
+    let mut greeks = numerical_greeks(bs_params, |p| simple_chooser_premium(p, choice_time));
+    greeks.theta = calendar_theta(bs_params, choice_time, |p, shift| simple_chooser_premium(p, choice_time + shift));
+    greeks
+}
+
+/// Complex chooser premium (Rubinstein). Strike and time to expiry of `bs_params` are not used.
+pub fn complex_chooser_premium(bs_params: &BlackScholesParams, chooser: &ComplexChooserParams) -> f64 {
+    let t = chooser.choice_time;
+    let call = |s: f64, tte: f64| BlackScholesParams { price: s, strike: chooser.call_strike, time_to_expiry: tte, ..*bs_params };
+    let put = |s: f64, tte: f64| BlackScholesParams { price: s, strike: chooser.put_strike, time_to_expiry: tte, ..*bs_params };
+
+    // underlying price at choice time, where call and put are worth the same
+    let func = |s: f64| call_premium(&call(s, chooser.call_expiry - t)) - put_premium(&put(s, chooser.put_expiry - t));
+    let critical = critical_price(func, bs_params.price);
+
+    let vol = bs_params.vol;
+    let d1 = d1(&BlackScholesParams { strike: critical, time_to_expiry: t, ..*bs_params });
+    let d2 = d1 - vol * t.sqrt();
+    let call_params = call(bs_params.price, chooser.call_expiry);
+    let put_params = put(bs_params.price, chooser.put_expiry);
+    let y1 = crate::black_scholes::d1(&call_params);
+    let y2 = crate::black_scholes::d1(&put_params);
+    let rho1 = (t / chooser.call_expiry).sqrt();
+    let rho2 = (t / chooser.put_expiry).sqrt();
+
+    let q = bs_params.div_yield;
+    let r = bs_params.rate;
+    let s = bs_params.price;
+    s * (-q * chooser.call_expiry).exp() * bivariate_normal_cdf(d1, y1, rho1)
+        - chooser.call_strike * (-r * chooser.call_expiry).exp() * bivariate_normal_cdf(d2, y1 - vol * chooser.call_expiry.sqrt(), rho1)
+        - s * (-q * chooser.put_expiry).exp() * bivariate_normal_cdf(-d1, -y2, rho2)
+        + chooser.put_strike * (-r * chooser.put_expiry).exp() * bivariate_normal_cdf(-d2, -y2 + vol * chooser.put_expiry.sqrt(), rho2)
+}
+
+/// Complex chooser premium with greeks, theta accounts for choice time and both option expiries
+pub fn complex_chooser_greeks(bs_params: &BlackScholesParams, chooser: &ComplexChooserParams) -> Greeks {
+    let mut greeks = numerical_greeks(bs_params, |p| complex_chooser_premium(p, chooser));
+    greeks.theta = calendar_theta(bs_params, chooser.choice_time, |p, shift| {
+        complex_chooser_premium(p, &ComplexChooserParams {
+            choice_time: chooser.choice_time + shift,
+            call_expiry: chooser.call_expiry + shift,
+            put_expiry: chooser.put_expiry + shift,
+            ..*chooser
+        })
+    });
+    greeks
+}
+
+/// Underlying price where monotonic `func` changes sign, found by bisection in bracket widened around `guess`.
+/// If there is no sign change (e.g. put is never worth the compound strike), the bracket end closer to zero
+/// of `func` is the limit of critical price.
+fn critical_price<F>(func: F, guess: f64) -> f64
+    where F: Fn(f64) -> f64 {
+    let (mut lo, mut hi) = (0.5 * guess, 2.0 * guess);
+    for _ in 0..BRACKET_STEPS {
+        if func(lo) * func(hi) <= 0.0 {
+            break;
+        }
+        lo *= 0.5;
+        hi *= 2.0;
+    }
+    bisection(&func, lo, hi, EPS * guess).unwrap_or(if func(lo).abs() < func(hi).abs() {lo} else {hi})
+}
+
+/// Theta of the option with intermediate date, shifting option expiry and intermediate date by the same time
+fn calendar_theta<F>(bs_params: &BlackScholesParams, first_date: f64, pricer: F) -> f64
+    where F: Fn(&BlackScholesParams, f64) -> f64 {
+    let h = (0.5 * first_date).min(0.00001);
+    let bump = |shift: f64| pricer(&BlackScholesParams { time_to_expiry: bs_params.time_to_expiry + shift, ..*bs_params }, shift);
+    -(bump(h) - bump(-h)) / (2.0 * h)
+}
@@ -0,0 +1,121 @@
+#![cfg(test)]
+use std::f64::consts::PI;
+use rv::prelude::*;
+use crate::black_scholes::*;
+use crate::math::{bivariate_normal_cdf, simpson};
+use crate::compound::*;
+
+const EPS: f64 = 0.0001;
+
+#[test]
+fn test_bivariate_normal_cdf() {
+    let n: Gaussian = Gaussian::standard();
+    // independent variables
+    for (x, y) in [(0.3, -1.2), (-2.0, 1.5), (1.0, 1.0)] {
+        assert!((bivariate_normal_cdf(x, y, 0.0) - n.cdf(&x) * n.cdf(&y)).abs() < 1e-14);
+    }
+    // orthant probabilities
+    for rho in [-0.99, -0.7, -0.2, 0.4, 0.8, 0.95, 0.999] {
+        let expected = 0.25 + f64::asin(rho) / (2.0 * PI);
+        assert!((bivariate_normal_cdf(0.0, 0.0, rho) - expected).abs() < 1e-14);
+    }
+    // limiting correlations
+    assert!((bivariate_normal_cdf(0.5, -0.3, 1.0) - n.cdf(&-0.3)).abs() < 1e-14);
+    assert!((bivariate_normal_cdf(0.5, -0.3, -1.0) - (n.cdf(&0.5) - n.cdf(&0.3))).abs() < 1e-14);
+    // symmetry M(x, y, rho) + M(x, -y, -rho) = N(x)
+    for rho in [-0.95, -0.5, 0.3, 0.9] {
+        let sum = bivariate_normal_cdf(0.7, 1.3, rho) + bivariate_normal_cdf(0.7, -1.3, -rho);
+        assert!((sum - n.cdf(&0.7)).abs() < 1e-14);
+    }
+}
+
+#[test]
+fn test_compound_parity() {
+    let bs_params = BlackScholesParams{price: 500.0, strike: 520.0, div_yield: 0.03, rate: 0.08, vol: 0.35, time_to_expiry: 0.5};
+    let compound = CompoundParams{strike: 50.0, time_to_expiry: 0.25};
+    let discounted_strike = compound.strike * (-bs_params.rate * compound.time_to_expiry).exp();
+    for is_underlying_call in [true, false] {
+        let call = compound_premium(true, is_underlying_call, &bs_params, &compound);
+        let put = compound_premium(false, is_underlying_call, &bs_params, &compound);
+        let underlying = generic_black_scholes(is_underlying_call, &bs_params);
+        assert!((call - put - underlying + discounted_strike).abs() < EPS);
+        assert!(call > 0.0 && put > 0.0);
+        if is_underlying_call {
+            // Haug, The Complete Guide to Option Pricing Formulas
+            assert!((put - 21.1965).abs() < 0.001);
+        }
+    }
+}
+
+#[test]
+fn test_compound_limits() {
+    // compound with zero strike is the underlying option itself
+    let bs_params = BlackScholesParams{price: 100.0, strike: 105.0, div_yield: 0.0, rate: 0.05, vol: 0.25, time_to_expiry: 1.0};
+    let compound = CompoundParams{strike: 1e-8, time_to_expiry: 0.5};
+    assert!((compound_premium(true, true, &bs_params, &compound) - call_premium(&bs_params)).abs() < EPS);
+    assert!((compound_premium(true, false, &bs_params, &compound) - put_premium(&bs_params)).abs() < EPS);
+}
+
+#[test]
+fn test_simple_chooser() {
+    // Haug, The Complete Guide to Option Pricing Formulas
+    let bs_params = BlackScholesParams{price: 50.0, strike: 50.0, div_yield: 0.0, rate: 0.08, vol: 0.25, time_to_expiry: 0.5};
+    assert!((simple_chooser_premium(&bs_params, 0.25) - 6.1071).abs() < EPS);
+    // choosing at expiry is a straddle, choosing now is the more expensive option
+    let straddle = call_premium(&bs_params) + put_premium(&bs_params);
+    assert!((simple_chooser_premium(&bs_params, 0.5) - straddle).abs() < EPS);
+    assert!((simple_chooser_premium(&bs_params, 1e-10) - call_premium(&bs_params)).abs() < EPS);
+}
+
+#[test]
+fn test_complex_chooser() {
+    // Haug, The Complete Guide to Option Pricing Formulas
+    let bs_params = BlackScholesParams{price: 50.0, strike: 0.0, div_yield: 0.05, rate: 0.1, vol: 0.35, time_to_expiry: 0.0};
+    let chooser = ComplexChooserParams{choice_time: 0.25, call_strike: 55.0, call_expiry: 0.5, put_strike: 48.0, put_expiry: 0.5833};
+    assert!((complex_chooser_premium(&bs_params, &chooser) - 6.0508).abs() < 0.001);
+    // with equal strikes and expiries it is a simple chooser
+    let same = ComplexChooserParams{call_strike: 50.0, put_strike: 50.0, put_expiry: 0.5, ..chooser};
+    let simple = simple_chooser_premium(&BlackScholesParams{strike: 50.0, time_to_expiry: 0.5, ..bs_params}, 0.25);
+    assert!((complex_chooser_premium(&bs_params, &same) - simple).abs() < EPS);
+}
+
+#[test]
+fn test_chooser_greeks() {
+    let bs_params = BlackScholesParams{price: 50.0, strike: 50.0, div_yield: 0.0, rate: 0.08, vol: 0.25, time_to_expiry: 0.5};
+    // with choice at expiry the chooser is a straddle
+    let greeks = simple_chooser_greeks(&bs_params, 0.5 - 1e-9);
+    assert!((greeks.delta - call_delta(&bs_params) - put_delta(&bs_params)).abs() < EPS);
+    assert!((greeks.gamma - 2.0 * gamma(&bs_params)).abs() < EPS);
+    assert!((greeks.vega - 2.0 * vega(&bs_params)).abs() < EPS);
+    assert!((greeks.theta - call_theta(&bs_params) - put_theta(&bs_params)).abs() < 0.001);
+
+    let compound = compound_greeks(true, true, &bs_params, &CompoundParams{strike: 3.0, time_to_expiry: 0.25});
+    assert!(compound.delta > 0.0 && compound.delta < call_delta(&bs_params));
+    assert!(compound.gamma > 0.0 && compound.vega > 0.0 && compound.theta < 0.0);
+}
+
+#[test]
+fn test_compound_critical_price() {
+    // Newton method diverges from the initial guess, where out of the money put has almost zero delta
+    let bs_params = BlackScholesParams{price: 100.0, strike: 100.0, div_yield: 0.0, rate: 0.1, vol: 0.02, time_to_expiry: 1.5};
+    let n: Gaussian = Gaussian::standard();
+    let integrated = |compound: &CompoundParams| {
+        let t1 = compound.time_to_expiry;
+        let payoff = |z: f64| {
+            let s = bs_params.price * ((bs_params.rate - 0.5 * bs_params.vol * bs_params.vol) * t1 + bs_params.vol * t1.sqrt() * z).exp();
+            let put = put_premium(&BlackScholesParams{price: s, time_to_expiry: bs_params.time_to_expiry - t1, ..bs_params});
+            (put - compound.strike).max(0.0) * n.pdf(&z)
+        };
+        (-bs_params.rate * t1).exp() * simpson(payoff, -10.0, 10.0, 20000)
+    };
+    let compound = CompoundParams{strike: 0.05, time_to_expiry: 0.5};
+    let premium = compound_premium(true, false, &bs_params, &compound);
+    assert!(premium.is_finite() && premium > 0.0);
+    assert!((premium - integrated(&compound)).abs() < EPS);
+
+    // put is never worth more than discounted strike, so call on it is never exercised
+    let never = CompoundParams{strike: 95.0, time_to_expiry: 0.5};
+    assert!(compound_premium(true, false, &bs_params, &never).abs() < 1e-10);
+    let put = compound_premium(false, false, &bs_params, &never);
+    assert!((put - (95.0 * (-0.05f64).exp() - put_premium(&bs_params))).abs() < EPS);
+}
@@ -0,0 +1,119 @@
+//! module with risk neutral density of underlying price at expiry implied by option prices (Breeden and Litzenberger):
+//! density is discounted second derivative of call premium by strike, distribution function is
+//! `1 + exp(rT) dC/dK`.
+//!
+//! # Example
+//! ```
+//! use ivol::black_scholes::*;
+//! use ivol::density::*;
+//!
+//! let bs_params = BlackScholesParams{price: 100.0, strike: 0.0, div_yield: 0.0, rate: 0.03, vol: 0.3, time_to_expiry: 0.25};
+//! let strikes: Vec<f64> = (1..=1000).map(|k| k as f64 * 0.25).collect();
+//! let density = RiskNeutralDensity::from_smile(&bs_params, &strikes, |k| 0.3 - 0.1 * (k / 100.0).ln()).unwrap();
+//! // probability of 10% drop by expiry
+//! let p = density.cdf_at(90.0);
+//! assert!(p > 0.0 && p < 1.0);
+//! ```
+use crate::black_scholes::*;
+use crate::math::lin_interp;
+
+/// Relative strike bump for finite differences of smile premiums
+const STRIKE_BUMP: f64 = 0.001;
+
+/// Implied probability density and distribution function of the underlying price at expiry on a strike grid
+#[derive(Debug, Clone)]
+pub struct RiskNeutralDensity {
+    pub strikes: Vec<f64>,
+    pub density: Vec<f64>,
+    pub cdf: Vec<f64>
+}
+
+/// Moments of risk neutral distribution
+#[derive(Debug, Clone, Copy)]
+pub struct DensityMoments {
+    pub mean: f64,
+    pub variance: f64,
+    pub skewness: f64,
+    /// excess kurtosis, zero for normal distribution
+    pub kurtosis: f64
+}
+
+impl RiskNeutralDensity {
+
+    /// Density from implied volatility smile, where `vol` gives implied volatility for a strike.
+    /// Strike derivatives of `call_premium` are calculated with central differences at each strike of the grid.
+    /// Strike and volatility of `bs_params` are not used.
+    pub fn from_smile<F>(bs_params: &BlackScholesParams, strikes: &[f64], vol: F) -> Result<RiskNeutralDensity, String>
+        where F: Fn(f64) -> f64 {
+        check_strikes(strikes, 2)?;
+        let growth = (bs_params.rate * bs_params.time_to_expiry).exp();
+        let premium = |k: f64| call_premium(&BlackScholesParams { strike: k, vol: vol(k), ..*bs_params });
+
+        let mut density = Vec::with_capacity(strikes.len());
+        let mut cdf = Vec::with_capacity(strikes.len());
+        for k in strikes.iter() {
+            let h = STRIKE_BUMP * k;
+            let (up, mid, down) = (premium(k + h), premium(*k), premium(k - h));
+            density.push(growth * (up - 2.0 * mid + down) / (h * h));
+            cdf.push(1.0 + growth * (up - down) / (2.0 * h));
+        }
+        Ok(RiskNeutralDensity { strikes: strikes.to_vec(), density, cdf })
+    }
+
+    /// Density from call premiums on increasing strikes (market quotes or converted from puts with `callput_price`).
+    /// Finite differences need neighbouring strikes, so the first and the last strike are dropped.
+    pub fn from_prices(bs_params: &BlackScholesParams, strikes: &[f64], call_premiums: &[f64]) -> Result<RiskNeutralDensity, String> {
+        check_strikes(strikes, 3)?;
+        if strikes.len() != call_premiums.len() {
+            return Err("number of strikes and premiums must be the same".to_string());
+        }
+        let growth = (bs_params.rate * bs_params.time_to_expiry).exp();
+
+        let mut density = Vec::with_capacity(strikes.len() - 2);
+        let mut cdf = Vec::with_capacity(strikes.len() - 2);
+        for i in 1..strikes.len() - 1 {
+            let (h1, h2) = (strikes[i] - strikes[i - 1], strikes[i + 1] - strikes[i]);
+            let (down, mid, up) = (call_premiums[i - 1], call_premiums[i], call_premiums[i + 1]);
+            // three point differences on non-uniform grid
+            density.push(growth * 2.0 * ((up - mid) / h2 - (mid - down) / h1) / (h1 + h2));
+            let slope = (h1 * h1 * up - h2 * h2 * down + (h2 * h2 - h1 * h1) * mid) / (h1 * h2 * (h1 + h2));
+            cdf.push(1.0 + growth * slope);
+        }
+        Ok(RiskNeutralDensity { strikes: strikes[1..strikes.len() - 1].to_vec(), density, cdf })
+    }
+
+    /// Probability that the underlying price at expiry is below `level`, interpolated on the strike grid
+    pub fn cdf_at(&self, level: f64) -> f64 {
+        lin_interp(&self.strikes, &self.cdf, level).clamp(0.0, 1.0)
+    }
+
+    /// Moments of the distribution, integrated with trapezoidal rule over the strike grid
+    /// and normalized by probability mass inside the grid
+    pub fn moments(&self) -> DensityMoments {
+        let integrate = |f: &dyn Fn(f64) -> f64| {
+            self.strikes.windows(2).zip(self.density.windows(2))
+                .map(|(k, p)| 0.5 * (k[1] - k[0]) * (f(k[0]) * p[0] + f(k[1]) * p[1]))
+                .sum::<f64>()
+        };
+        let mass = integrate(&|_| 1.0);
+        let mean = integrate(&|k| k) / mass;
+        let central = |n: i32| integrate(&|k| (k - mean).powi(n)) / mass;
+        let variance = central(2);
+        DensityMoments {
+            mean,
+            variance,
+            skewness: central(3) / variance.powf(1.5),
+            kurtosis: central(4) / (variance * variance) - 3.0
+        }
+    }
+}
+
+fn check_strikes(strikes: &[f64], min_len: usize) -> Result<(), String> {
+    if strikes.len() < min_len {
+        return Err("not enough strikes".to_string());
+    }
+    if strikes[0] <= 0.0 || strikes.windows(2).any(|w| w[1] <= w[0]) {
+        return Err("strikes must be positive and increasing".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+#![cfg(test)]
+use rv::prelude::*;
+use crate::black_scholes::*;
+use crate::density::*;
+
+fn market() -> BlackScholesParams {
+    BlackScholesParams { price: 100.0, strike: 0.0, rate: 0.04, div_yield: 0.01, vol: 0.25, time_to_expiry: 0.5 }
+}
+
+fn strikes(step: f64) -> Vec<f64> {
+    let n = (400.0 / step) as usize;
+    (1..=n).map(|i| i as f64 * step).collect()
+}
+
+#[test]
+fn test_lognormal_density() {
+    // without smile the density is lognormal
+    let p = market();
+    let density = RiskNeutralDensity::from_smile(&p, &strikes(0.25), |_| p.vol).unwrap();
+    let n: Gaussian = Gaussian::standard();
+    for k in [70.0, 100.0, 130.0] {
+        assert!((density.cdf_at(k) - n.cdf(&-d2(&BlackScholesParams { strike: k, ..p }))).abs() < 0.0001);
+    }
+
+    let s = p.vol * p.vol * p.time_to_expiry;
+    let forward = p.price * ((p.rate - p.div_yield) * p.time_to_expiry).exp();
+    let moments = density.moments();
+    assert!((moments.mean - forward).abs() < 0.001);
+    assert!((moments.variance - forward * forward * (s.exp() - 1.0)).abs() < 0.01);
+    assert!((moments.skewness - (s.exp() + 2.0) * (s.exp() - 1.0).sqrt()).abs() < 0.001);
+    let kurtosis = (4.0 * s).exp() + 2.0 * (3.0 * s).exp() + 3.0 * (2.0 * s).exp() - 6.0;
+    assert!((moments.kurtosis - kurtosis).abs() < 0.01);
+}
+
+#[test]
+fn test_density_from_prices() {
+    let p = market();
+    let smile = |k: f64| 0.25 - 0.05 * ((k / 100.0).ln() / 0.3).tanh();
+    let ks = strikes(0.5);
+    let premiums: Vec<f64> = ks.iter().map(|k| call_premium(&BlackScholesParams { strike: *k, vol: smile(*k), ..p })).collect();
+    let from_prices = RiskNeutralDensity::from_prices(&p, &ks, &premiums).unwrap();
+    let from_smile = RiskNeutralDensity::from_smile(&p, &ks, smile).unwrap();
+    assert_eq!(from_prices.strikes.len(), ks.len() - 2);
+    for k in [60.0, 90.0, 100.0, 125.0] {
+        assert!((from_prices.cdf_at(k) - from_smile.cdf_at(k)).abs() < 0.001);
+    }
+
+    // density is a probability distribution, negative skew smile gives negatively skewed returns
+    assert!(from_smile.density.iter().all(|d| *d > -1e-6));
+    let moments = from_smile.moments();
+    let lognormal = RiskNeutralDensity::from_smile(&p, &ks, |_| 0.25).unwrap().moments();
+    assert!(moments.skewness < lognormal.skewness);
+    assert!((moments.mean - lognormal.mean).abs() < 0.01);
+
+    assert!(RiskNeutralDensity::from_prices(&p, &[90.0, 100.0], &[12.0, 6.0]).is_err());
+    assert!(RiskNeutralDensity::from_prices(&p, &[90.0, 100.0, 95.0], &[12.0, 6.0, 8.0]).is_err());
+}
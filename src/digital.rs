@@ -0,0 +1,117 @@
+//! module with digital (binary) options pricing: cash-or-nothing and asset-or-nothing calls and puts with sensitivities.
+//! Cash-or-nothing options pay one unit of cash, asset-or-nothing options pay one unit of the underlying asset.
+//! Vega and rho are scaled the same way as vanilla [`crate::black_scholes::vega`] and [`crate::black_scholes::call_rho`].
+use rv::prelude::*;
+use crate::black_scholes::*;
+
+/// Cash-or-nothing option premium
+pub fn cash_digital_premium(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let n: Gaussian = Gaussian::standard();
+    (-bs_params.rate * bs_params.time_to_expiry).exp() * n.cdf(&(sign * d2(bs_params)))
+}
+
+/// Asset-or-nothing option premium
+pub fn asset_digital_premium(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let n: Gaussian = Gaussian::standard();
+    bs_params.price * (-bs_params.div_yield * bs_params.time_to_expiry).exp() * n.cdf(&(sign * d1(bs_params)))
+}
+
+/// Delta sensitivity of cash-or-nothing option
+pub fn cash_digital_delta(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    sign * cash_density(bs_params) / (bs_params.price * vol_sqrt_t(bs_params))
+}
+
+/// Gamma sensitivity of cash-or-nothing option
+pub fn cash_digital_gamma(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let vt = vol_sqrt_t(bs_params);
+    -sign * cash_density(bs_params) * d1(bs_params) / (bs_params.price * bs_params.price * vt * vt)
+}
+
+/// Vega sensitivity of cash-or-nothing option
+pub fn cash_digital_vega(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    -0.01 * sign * cash_density(bs_params) * d1(bs_params) / bs_params.vol
+}
+
+/// Rho sensitivity of cash-or-nothing option
+pub fn cash_digital_rho(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let t = bs_params.time_to_expiry;
+    0.01 * (-t * cash_digital_premium(is_call, bs_params) + sign * cash_density(bs_params) * t.sqrt() / bs_params.vol)
+}
+
+/// Theta sensitivity of cash-or-nothing option
+pub fn cash_digital_theta(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let t = bs_params.time_to_expiry;
+    let drift = bs_params.rate - bs_params.div_yield - 0.5 * bs_params.vol * bs_params.vol;
+    let dd2_dt = (drift * t - (bs_params.price / bs_params.strike).ln()) / (2.0 * bs_params.vol * t.powf(1.5));
+    bs_params.rate * cash_digital_premium(is_call, bs_params) - sign * cash_density(bs_params) * dd2_dt
+}
+
+/// Delta sensitivity of asset-or-nothing option
+pub fn asset_digital_delta(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let n: Gaussian = Gaussian::standard();
+    let dd = (-bs_params.div_yield * bs_params.time_to_expiry).exp();
+    sign * asset_density(bs_params) / (bs_params.price * vol_sqrt_t(bs_params)) + dd * n.cdf(&(sign * d1(bs_params)))
+}
+
+/// Gamma sensitivity of asset-or-nothing option
+pub fn asset_digital_gamma(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let vt = vol_sqrt_t(bs_params);
+    sign * asset_density(bs_params) / (bs_params.price * bs_params.price * vt) * (1.0 - d1(bs_params) / vt)
+}
+
+/// Vega sensitivity of asset-or-nothing option
+pub fn asset_digital_vega(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    -0.01 * sign * asset_density(bs_params) * d2(bs_params) / bs_params.vol
+}
+
+/// Rho sensitivity of asset-or-nothing option
+pub fn asset_digital_rho(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    0.01 * sign * asset_density(bs_params) * bs_params.time_to_expiry.sqrt() / bs_params.vol
+}
+
+/// Theta sensitivity of asset-or-nothing option
+pub fn asset_digital_theta(is_call: bool, bs_params: &BlackScholesParams) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let t = bs_params.time_to_expiry;
+    let drift = bs_params.rate - bs_params.div_yield + 0.5 * bs_params.vol * bs_params.vol;
+    let dd1_dt = (drift * t - (bs_params.price / bs_params.strike).ln()) / (2.0 * bs_params.vol * t.powf(1.5));
+    bs_params.div_yield * asset_digital_premium(is_call, bs_params) - sign * asset_density(bs_params) * dd1_dt
+}
+
+/// Smile consistent cash-or-nothing premium, which is the strike derivative of vanilla premium
+/// with strike dependent volatility.
+/// * vol_slope - derivative of implied volatility with respect to strike at the option strike
+pub fn smile_cash_digital_premium(is_call: bool, bs_params: &BlackScholesParams, vol_slope: f64) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    cash_digital_premium(is_call, bs_params) - sign * dtv_dvol(bs_params) * vol_slope
+}
+
+/// Discounted normal density at d2
+#[inline]
+fn cash_density(bs_params: &BlackScholesParams) -> f64 {
+    let n: Gaussian = Gaussian::standard();
+    (-bs_params.rate * bs_params.time_to_expiry).exp() * n.pdf(&d2(bs_params))
+}
+
+/// Discounted price times normal density at d1
+#[inline]
+fn asset_density(bs_params: &BlackScholesParams) -> f64 {
+    let n: Gaussian = Gaussian::standard();
+    bs_params.price * (-bs_params.div_yield * bs_params.time_to_expiry).exp() * n.pdf(&d1(bs_params))
+}
+
+#[inline]
+fn vol_sqrt_t(bs_params: &BlackScholesParams) -> f64 {
+    bs_params.vol * bs_params.time_to_expiry.sqrt()
+}
@@ -0,0 +1,94 @@
+#![cfg(test)]
+use crate::black_scholes;
+use crate::black_scholes::BlackScholesParams;
+use crate::digital::*;
+
+const BUMP: f64 = 0.00001;
+const EPS: f64 = 0.0001;
+
+fn bs_params() -> BlackScholesParams {
+    BlackScholesParams { price: 105.0, strike: 100.0, rate: 0.03, div_yield: 0.015, vol: 0.22, time_to_expiry: 0.8 }
+}
+
+/// central finite difference of `f` along parameter modified by `bump`
+fn diff<F, B>(f: F, bump: B, h: f64) -> f64
+    where F: Fn(&BlackScholesParams) -> f64, B: Fn(&BlackScholesParams, f64) -> BlackScholesParams {
+    let p = bs_params();
+    (f(&bump(&p, h)) - f(&bump(&p, -h))) / (2.0 * h)
+}
+
+#[test]
+fn test_digital_replication() {
+    let p = bs_params();
+    let discount = (-p.rate * p.time_to_expiry).exp();
+    let forward_value = p.price * (-p.div_yield * p.time_to_expiry).exp();
+
+    assert!((cash_digital_premium(true, &p) + cash_digital_premium(false, &p) - discount).abs() < 1e-12);
+    assert!((asset_digital_premium(true, &p) + asset_digital_premium(false, &p) - forward_value).abs() < 1e-12);
+
+    // vanilla is a combination of asset and cash digitals
+    let call = asset_digital_premium(true, &p) - p.strike * cash_digital_premium(true, &p);
+    let put = p.strike * cash_digital_premium(false, &p) - asset_digital_premium(false, &p);
+    assert!((call - black_scholes::call_premium(&p)).abs() < 1e-10);
+    assert!((put - black_scholes::put_premium(&p)).abs() < 1e-10);
+}
+
+#[test]
+fn test_cash_digital_greeks() {
+    for is_call in [true, false].iter() {
+        let is_call = *is_call;
+        let f = |p: &BlackScholesParams| cash_digital_premium(is_call, p);
+        let p = bs_params();
+
+        let delta = diff(f, |c, h| BlackScholesParams { price: c.price + h, ..*c }, BUMP);
+        assert!((cash_digital_delta(is_call, &p) - delta).abs() < EPS);
+        let gamma = diff(|c: &BlackScholesParams| cash_digital_delta(is_call, c), |c, h| BlackScholesParams { price: c.price + h, ..*c }, BUMP);
+        assert!((cash_digital_gamma(is_call, &p) - gamma).abs() < EPS);
+        let vega = diff(f, |c, h| BlackScholesParams { vol: c.vol + h, ..*c }, BUMP) / 100.0;
+        assert!((cash_digital_vega(is_call, &p) - vega).abs() < EPS);
+        let rho = diff(f, |c, h| BlackScholesParams { rate: c.rate + h, ..*c }, BUMP) / 100.0;
+        assert!((cash_digital_rho(is_call, &p) - rho).abs() < EPS);
+        let theta = -diff(f, |c, h| BlackScholesParams { time_to_expiry: c.time_to_expiry + h, ..*c }, BUMP);
+        assert!((cash_digital_theta(is_call, &p) - theta).abs() < EPS);
+    }
+}
+
+#[test]
+fn test_asset_digital_greeks() {
+    for is_call in [true, false].iter() {
+        let is_call = *is_call;
+        let f = |p: &BlackScholesParams| asset_digital_premium(is_call, p);
+        let p = bs_params();
+
+        let delta = diff(f, |c, h| BlackScholesParams { price: c.price + h, ..*c }, BUMP);
+        assert!((asset_digital_delta(is_call, &p) - delta).abs() < EPS);
+        let gamma = diff(|c: &BlackScholesParams| asset_digital_delta(is_call, c), |c, h| BlackScholesParams { price: c.price + h, ..*c }, BUMP);
+        assert!((asset_digital_gamma(is_call, &p) - gamma).abs() < EPS);
+        let vega = diff(f, |c, h| BlackScholesParams { vol: c.vol + h, ..*c }, BUMP) / 100.0;
+        assert!((asset_digital_vega(is_call, &p) - vega).abs() < EPS);
+        let rho = diff(f, |c, h| BlackScholesParams { rate: c.rate + h, ..*c }, BUMP) / 100.0;
+        assert!((asset_digital_rho(is_call, &p) - rho).abs() < EPS);
+        let theta = -diff(f, |c, h| BlackScholesParams { time_to_expiry: c.time_to_expiry + h, ..*c }, BUMP);
+        assert!((asset_digital_theta(is_call, &p) - theta).abs() < EPS);
+    }
+}
+
+#[test]
+fn test_smile_consistent_digital() {
+    // linear smile in strike
+    let slope = -0.002;
+    let smile = |k: f64| 0.22 + slope * (k - 100.0);
+    let h = 0.01;
+    let p = bs_params();
+    let vanilla = |k: f64, is_call: bool| {
+        let bumped = BlackScholesParams { strike: k, vol: smile(k), ..p };
+        if is_call {black_scholes::call_premium(&bumped)} else {black_scholes::put_premium(&bumped)}
+    };
+
+    let call_spread = (vanilla(p.strike - h, true) - vanilla(p.strike + h, true)) / (2.0 * h);
+    let put_spread = (vanilla(p.strike + h, false) - vanilla(p.strike - h, false)) / (2.0 * h);
+    assert!((smile_cash_digital_premium(true, &p, slope) - call_spread).abs() < EPS);
+    assert!((smile_cash_digital_premium(false, &p, slope) - put_spread).abs() < EPS);
+    // negative skew makes digital call more expensive than flat vol price
+    assert!(smile_cash_digital_premium(true, &p, slope) > cash_digital_premium(true, &p));
+}
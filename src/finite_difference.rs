@@ -0,0 +1,216 @@
+//! module with finite difference (Crank-Nicolson) solver of one dimensional Black/Scholes PDE for european and american options.
+//!
+//! # Example
+//! ```
+//! use ivol::black_scholes::*;
+//! use ivol::finite_difference::*;
+//!
+//! let bs_params = BlackScholesParams{price: 100.0, strike: 100.0, div_yield: 0.0, rate: 0.05, vol: 0.2, time_to_expiry: 1.0};
+//! let fd_params = FdParams{space_steps: 200, time_steps: 200, rannacher_steps: 2, concentration: 0.1};
+//!
+//! let european = fd_premium(false, false, &bs_params, &fd_params);
+//! let american = fd_premium(false, true, &bs_params, &fd_params);
+//! assert!((european.premium - put_premium(&bs_params)).abs() < 0.01);
+//! assert!(american.premium > european.premium);
+//! ```
+use crate::black_scholes::BlackScholesParams;
+
+/// Relaxation parameter of projected SOR
+const PSOR_OMEGA: f64 = 1.2;
+/// Convergence tolerance of projected SOR
+const PSOR_EPS: f64 = 1e-10;
+/// Maximum number of projected SOR iterations per time step
+const PSOR_ITER: usize = 10000;
+/// Width of the grid in standard deviations of log price
+const GRID_WIDTH: f64 = 6.0;
+
+/// Parameters of finite difference grid
+#[derive(Debug, Clone, Copy)]
+pub struct FdParams {
+    /// number of intervals of underlying price grid
+    pub space_steps: usize,
+    /// number of time steps
+    pub time_steps: usize,
+    /// number of first time steps replaced with two fully implicit half steps (Rannacher smoothing)
+    pub rannacher_steps: usize,
+    /// grid concentration around strike as fraction of the strike, smaller values give denser grid near strike
+    pub concentration: f64
+}
+
+/// Result of finite difference pricing, sensitivities are taken from the grid
+#[derive(Debug, Clone, Copy)]
+pub struct FdResult {
+    pub premium: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    /// premium change per year of calendar time
+    pub theta: f64
+}
+
+/// Prices option by solving Black/Scholes PDE with Crank-Nicolson scheme.
+/// American exercise is handled with projected SOR on each time step.
+pub fn fd_premium(is_call: bool, is_american: bool, bs_params: &BlackScholesParams, fd_params: &FdParams) -> FdResult {
+    let sign = if is_call {1.0} else {-1.0};
+    let strike = bs_params.strike;
+    let grid = build_grid(bs_params, fd_params);
+    let n = grid.len();
+    let dt = bs_params.time_to_expiry / fd_params.time_steps.max(1) as f64;
+    let payoff: Vec<f64> = grid.iter().map(|s| (sign * (s - strike)).max(0.0)).collect();
+
+    // spatial operator L V_i = a_i V_{i-1} + b_i V_i + c_i V_{i+1}
+    let var = bs_params.vol * bs_params.vol;
+    let carry = bs_params.rate - bs_params.div_yield;
+    let mut a = vec![0.0; n];
+    let mut b = vec![-bs_params.rate; n];
+    let mut c = vec![0.0; n];
+    for i in 1..n - 1 {
+        let (hm, hp) = (grid[i] - grid[i - 1], grid[i + 1] - grid[i]);
+        let diff = 0.5 * var * grid[i] * grid[i];
+        let conv = carry * grid[i];
+        a[i] = diff * 2.0 / (hm * (hm + hp)) - conv * hp / (hm * (hm + hp));
+        b[i] = -diff * 2.0 / (hm * hp) + conv * (hp - hm) / (hm * hp) - bs_params.rate;
+        c[i] = diff * 2.0 / (hp * (hm + hp)) + conv * hm / (hp * (hm + hp));
+    }
+
+    let boundary = |tau: f64| {
+        let s_max = grid[n - 1];
+        let european = if is_call {
+            s_max * (-bs_params.div_yield * tau).exp() - strike * (-bs_params.rate * tau).exp()
+        } else {
+            0.0
+        };
+        if is_american {european.max(payoff[n - 1])} else {european}
+    };
+
+    let mut v = payoff.clone();
+    let mut prev = v.clone();
+    let mut tau = 0.0;
+    for step in 0..fd_params.time_steps.max(1) {
+        prev.copy_from_slice(&v);
+        if step < fd_params.rannacher_steps {
+            for _ in 0..2 {
+                tau += 0.5 * dt;
+                v = theta_step(&v, &a, &b, &c, 1.0, 0.5 * dt, boundary(tau), is_american, &payoff);
+            }
+        } else {
+            tau += dt;
+            v = theta_step(&v, &a, &b, &c, 0.5, dt, boundary(tau), is_american, &payoff);
+        }
+    }
+
+    let (premium, delta, gamma) = interpolate(&grid, &v, bs_params.price);
+    let prev_premium = interpolate(&grid, &prev, bs_params.price).0;
+    FdResult { premium, delta, gamma, theta: (prev_premium - premium) / dt }
+}
+
+/// One step of theta scheme `(I - theta dt L) V_new = (I + (1 - theta) dt L) V_old`
+#[allow(clippy::too_many_arguments)]
+fn theta_step(v: &[f64], a: &[f64], b: &[f64], c: &[f64], theta: f64, dt: f64, upper: f64, is_american: bool, payoff: &[f64]) -> Vec<f64> {
+    let n = v.len();
+    let ex = (1.0 - theta) * dt;
+    let mut rhs = vec![0.0; n];
+    for i in 0..n {
+        let left = if i > 0 {a[i] * v[i - 1]} else {0.0};
+        let right = if i < n - 1 {c[i] * v[i + 1]} else {0.0};
+        rhs[i] = v[i] + ex * (left + b[i] * v[i] + right);
+    }
+
+    // implicit system, last row is Dirichlet boundary condition
+    let im = theta * dt;
+    let lower: Vec<f64> = a.iter().map(|x| -im * x).collect();
+    let mut diag: Vec<f64> = b.iter().map(|x| 1.0 - im * x).collect();
+    let mut upper_diag: Vec<f64> = c.iter().map(|x| -im * x).collect();
+    diag[n - 1] = 1.0;
+    upper_diag[n - 1] = 0.0;
+    rhs[n - 1] = upper;
+
+    if is_american {
+        psor(&lower, &diag, &upper_diag, &rhs, payoff, v)
+    } else {
+        let mut l = lower;
+        l[n - 1] = 0.0;
+        thomas(&l, &diag, &upper_diag, &rhs)
+    }
+}
+
+/// Solves tridiagonal system with Thomas algorithm
+fn thomas(a: &[f64], b: &[f64], c: &[f64], d: &[f64]) -> Vec<f64> {
+    let n = d.len();
+    let mut cp = vec![0.0; n];
+    let mut dp = vec![0.0; n];
+    cp[0] = c[0] / b[0];
+    dp[0] = d[0] / b[0];
+    for i in 1..n {
+        let m = b[i] - a[i] * cp[i - 1];
+        cp[i] = c[i] / m;
+        dp[i] = (d[i] - a[i] * dp[i - 1]) / m;
+    }
+    let mut x = vec![0.0; n];
+    x[n - 1] = dp[n - 1];
+    for i in (0..n - 1).rev() {
+        x[i] = dp[i] - cp[i] * x[i + 1];
+    }
+    x
+}
+
+/// Solves linear complementarity problem `x >= payoff` for tridiagonal system with projected SOR
+fn psor(a: &[f64], b: &[f64], c: &[f64], d: &[f64], payoff: &[f64], guess: &[f64]) -> Vec<f64> {
+    let n = d.len();
+    let mut x = guess.to_vec();
+    x[n - 1] = d[n - 1];
+    for _ in 0..PSOR_ITER {
+        let mut error = 0.0;
+        for i in 0..n - 1 {
+            let left = if i > 0 {a[i] * x[i - 1]} else {0.0};
+            let gs = (d[i] - left - c[i] * x[i + 1]) / b[i];
+            let new = (x[i] + PSOR_OMEGA * (gs - x[i])).max(payoff[i]);
+            error += (new - x[i]) * (new - x[i]);
+            x[i] = new;
+        }
+        if error < PSOR_EPS * PSOR_EPS {
+            break;
+        }
+    }
+    x
+}
+
+/// Price grid from zero to several standard deviations above spot and strike,
+/// nodes are concentrated around strike with sinh transformation
+fn build_grid(bs_params: &BlackScholesParams, fd_params: &FdParams) -> Vec<f64> {
+    let strike = bs_params.strike;
+    let s_max = bs_params.price.max(strike) * (GRID_WIDTH * bs_params.vol * bs_params.time_to_expiry.sqrt()).exp();
+    let c = fd_params.concentration.max(1e-6) * strike;
+    let lo = (-strike / c).asinh();
+    let hi = ((s_max - strike) / c).asinh();
+    let n = fd_params.space_steps.max(3);
+    (0..=n).map(|i| {
+        let xi = lo + (hi - lo) * i as f64 / n as f64;
+        (strike + c * xi.sinh()).max(0.0)
+    }).collect()
+}
+
+/// Quadratic interpolation of grid values at point `x` with its first and second derivatives
+fn interpolate(grid: &[f64], v: &[f64], x: f64) -> (f64, f64, f64) {
+    let n = grid.len();
+    let mut i = grid.iter().position(|s| *s >= x).unwrap_or(n - 1);
+    i = i.clamp(1, n - 2);
+    // choose the nearest node as the middle of three point stencil
+    if i + 1 < n - 1 && (grid[i] - x).abs() > (grid[i + 1] - x).abs() {
+        i += 1;
+    }
+    let (x0, x1, x2) = (grid[i - 1], grid[i], grid[i + 1]);
+    let (v0, v1, v2) = (v[i - 1], v[i], v[i + 1]);
+
+    let l0 = ((x - x1) * (x - x2), (2.0 * x - x1 - x2), 2.0);
+    let l1 = ((x - x0) * (x - x2), (2.0 * x - x0 - x2), 2.0);
+    let l2 = ((x - x0) * (x - x1), (2.0 * x - x0 - x1), 2.0);
+    let d0 = (x0 - x1) * (x0 - x2);
+    let d1 = (x1 - x0) * (x1 - x2);
+    let d2 = (x2 - x0) * (x2 - x1);
+
+    (
+        v0 * l0.0 / d0 + v1 * l1.0 / d1 + v2 * l2.0 / d2,
+        v0 * l0.1 / d0 + v1 * l1.1 / d1 + v2 * l2.1 / d2,
+        v0 * l0.2 / d0 + v1 * l1.2 / d1 + v2 * l2.2 / d2
+    )
+}
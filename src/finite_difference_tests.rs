@@ -0,0 +1,56 @@
+#![cfg(test)]
+use crate::black_scholes;
+use crate::black_scholes::BlackScholesParams;
+use crate::finite_difference::*;
+
+const FD_PARAMS: FdParams = FdParams { space_steps: 300, time_steps: 200, rannacher_steps: 2, concentration: 0.1 };
+
+#[test]
+fn test_european_matches_black_scholes() {
+    for k in [80.0, 100.0, 125.0].iter() {
+        let bs_params = BlackScholesParams { price: 100.0, strike: *k, rate: 0.04, div_yield: 0.02, vol: 0.25, time_to_expiry: 0.75 };
+
+        let call = fd_premium(true, false, &bs_params, &FD_PARAMS);
+        assert!((call.premium - black_scholes::call_premium(&bs_params)).abs() < 0.01);
+        assert!((call.delta - black_scholes::call_delta(&bs_params)).abs() < 0.001);
+        assert!((call.gamma - black_scholes::gamma(&bs_params)).abs() < 0.0005);
+        assert!((call.theta - black_scholes::call_theta(&bs_params)).abs() < 0.05);
+
+        let put = fd_premium(false, false, &bs_params, &FD_PARAMS);
+        assert!((put.premium - black_scholes::put_premium(&bs_params)).abs() < 0.01);
+        assert!((put.delta - black_scholes::put_delta(&bs_params)).abs() < 0.001);
+        assert!((put.theta - black_scholes::put_theta(&bs_params)).abs() < 0.05);
+    }
+}
+
+#[test]
+fn test_rannacher_smooths_gamma() {
+    // short dated at-the-money option, where pure Crank-Nicolson oscillates
+    let bs_params = BlackScholesParams { price: 100.0, strike: 100.0, rate: 0.03, div_yield: 0.0, vol: 0.2, time_to_expiry: 0.05 };
+    let params = FdParams { time_steps: 20, ..FD_PARAMS };
+    let smooth = fd_premium(true, false, &bs_params, &params);
+    let gamma = black_scholes::gamma(&bs_params);
+    assert!((smooth.gamma - gamma).abs() < 0.01 * gamma);
+}
+
+#[test]
+fn test_american_put() {
+    // Hull's example: american put value 4.28
+    let bs_params = BlackScholesParams { price: 50.0, strike: 50.0, rate: 0.1, div_yield: 0.0, vol: 0.4, time_to_expiry: 5.0 / 12.0 };
+    let american = fd_premium(false, true, &bs_params, &FD_PARAMS);
+    assert!((american.premium - 4.28).abs() < 0.01);
+    assert!(american.premium > black_scholes::put_premium(&bs_params));
+    assert!(american.delta < 0.0 && american.delta > -1.0);
+
+    // deep in the money american put is worth its intrinsic value
+    let deep = fd_premium(false, true, &BlackScholesParams { price: 20.0, ..bs_params }, &FD_PARAMS);
+    assert!((deep.premium - 30.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_american_call_without_dividends() {
+    let bs_params = BlackScholesParams { price: 100.0, strike: 95.0, rate: 0.05, div_yield: 0.0, vol: 0.3, time_to_expiry: 1.0 };
+    let american = fd_premium(true, true, &bs_params, &FD_PARAMS);
+    let european = fd_premium(true, false, &bs_params, &FD_PARAMS);
+    assert!((american.premium - european.premium).abs() < 1e-6);
+}
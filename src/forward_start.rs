@@ -0,0 +1,28 @@
+//! module with forward start options (Rubinstein) and cliquets built from them.
+use crate::black_scholes::*;
+
+/// Forward start option premium. The option starts at `time_to_start` with strike set to `moneyness` times
+/// the underlying price at that time and expires at `bs_params.time_to_expiry`. Strike of `bs_params` is not used.
+pub fn forward_start_premium(is_call: bool, bs_params: &BlackScholesParams, time_to_start: f64, moneyness: f64) -> f64 {
+    // Black/Scholes premium is homogeneous in price and strike, so the option is worth
+    // dividend discounted spot units of today's option with relative strike
+    let started = BlackScholesParams {
+        strike: moneyness * bs_params.price,
+        time_to_expiry: bs_params.time_to_expiry - time_to_start,
+        ..*bs_params
+    };
+    (-bs_params.div_yield * time_to_start).exp() * generic_black_scholes(is_call, &started)
+}
+
+/// Cliquet (ratchet) premium as a strip of forward start options between consecutive reset times.
+/// First period starts now, `reset_times` are increasing period end times, the last one is the cliquet expiry.
+/// Strike and time to expiry of `bs_params` are not used.
+pub fn cliquet_premium(is_call: bool, bs_params: &BlackScholesParams, reset_times: &[f64], moneyness: f64) -> f64 {
+    let mut start = 0.0;
+    let mut premium = 0.0;
+    for end in reset_times.iter() {
+        premium += forward_start_premium(is_call, &BlackScholesParams { time_to_expiry: *end, ..*bs_params }, start, moneyness);
+        start = *end;
+    }
+    premium
+}
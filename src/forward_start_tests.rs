@@ -0,0 +1,29 @@
+#![cfg(test)]
+use crate::black_scholes;
+use crate::black_scholes::BlackScholesParams;
+use crate::forward_start::*;
+
+const EPS: f64 = 0.0001;
+
+#[test]
+fn test_forward_start_reference_value() {
+    // Haug's example: call starting in 3 months with strike 10% out of the money
+    let p = BlackScholesParams { price: 60.0, strike: 0.0, rate: 0.08, div_yield: 0.04, vol: 0.3, time_to_expiry: 1.0 };
+    assert!((forward_start_premium(true, &p, 0.25, 1.1) - 4.4064).abs() < 0.001);
+}
+
+#[test]
+fn test_spot_start_is_vanilla() {
+    let p = BlackScholesParams { price: 100.0, strike: 95.0, rate: 0.03, div_yield: 0.01, vol: 0.2, time_to_expiry: 0.75 };
+    assert!((forward_start_premium(true, &p, 0.0, 0.95) - black_scholes::call_premium(&p)).abs() < EPS);
+    assert!((forward_start_premium(false, &p, 0.0, 0.95) - black_scholes::put_premium(&p)).abs() < EPS);
+}
+
+#[test]
+fn test_cliquet() {
+    let p = BlackScholesParams { price: 100.0, strike: 0.0, rate: 0.03, div_yield: 0.0, vol: 0.2, time_to_expiry: 0.0 };
+    // without dividends all quarterly at-the-money forward starts of equal length are worth the same
+    let quarterly = cliquet_premium(true, &p, &[0.25, 0.5, 0.75, 1.0], 1.0);
+    let first = black_scholes::call_premium(&BlackScholesParams { strike: 100.0, time_to_expiry: 0.25, ..p });
+    assert!((quarterly - 4.0 * first).abs() < EPS);
+}
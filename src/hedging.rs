@@ -0,0 +1,235 @@
+//! module with delta hedging backtest: option position is hedged with the underlying along historical or
+//! simulated price path, rebalancing with [`call_delta`] or [`put_delta`] at implied volatility of the position
+//! according to hedging policy and paying transaction costs. P&L of hedged position shows realized against
+//! implied volatility and the price of discrete hedging.
+//!
+//! # Example
+//! ```
+//! use ivol::black_scholes::*;
+//! use ivol::hedging::*;
+//! use ivol::scenario::OptionPosition;
+//!
+//! let bs_params = BlackScholesParams{price: 100.0, strike: 100.0, div_yield: 0.0, rate: 0.03, vol: 0.2, time_to_expiry: 0.25};
+//! let position = OptionPosition{is_call: true, quantity: -100.0, bs_params};
+//! let paths = gbm_paths(100.0, 0.03, 0.2, 0.25, 63, 500, 7);
+//! let costs = TransactionCosts{proportional: 0.0005, fixed: 0.0};
+//!
+//! let daily = backtest_paths(&position, &paths, &HedgePolicy::FixedInterval(1), &costs).unwrap();
+//! let band = backtest_paths(&position, &paths, &HedgePolicy::DeltaBand(10.0), &costs).unwrap();
+//! assert!(band.mean_trades < daily.mean_trades);
+//! assert!(band.mean_costs < daily.mean_costs);
+//! ```
+use crate::black_scholes::*;
+use crate::chain::days_since_epoch;
+use crate::monte_carlo::*;
+use crate::scenario::OptionPosition;
+
+/// Days in year for dates of price path
+const DAYS_IN_YEAR: f64 = 365.0;
+
+/// Underlying prices at increasing times
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricePath {
+    /// times in years from the start of the path
+    pub times: Vec<f64>,
+    pub prices: Vec<f64>
+}
+
+/// Rebalancing rule of the hedge
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HedgePolicy {
+    /// rebalance to delta every given number of path points
+    FixedInterval(usize),
+    /// rebalance to delta when hedge differs from it by more than given number of underlying units
+    DeltaBand(f64),
+    /// rebalance to the nearest edge of Whalley-Wilmott no-transaction band around delta,
+    /// whose width depends on proportional costs, gamma and risk aversion
+    WhalleyWilmott { risk_aversion: f64 }
+}
+
+/// Costs of hedge trades
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TransactionCosts {
+    /// fraction of traded value, e.g. `0.0005` for 5 basis points
+    pub proportional: f64,
+    /// cost per trade
+    pub fixed: f64
+}
+
+/// Result of hedging along one path, amounts are valued at the end of the path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HedgeResult {
+    /// P&L of option bought or sold at implied volatility and the hedge, net of costs
+    pub pnl: f64,
+    /// P&L before transaction costs, zero for perfect replication
+    pub hedge_error: f64,
+    /// transaction costs paid
+    pub costs: f64,
+    /// number of hedge trades including the initial one
+    pub trades: usize
+}
+
+/// Distribution of hedging results over paths
+#[derive(Debug, Clone, PartialEq)]
+pub struct HedgeSummary {
+    pub results: Vec<HedgeResult>,
+    pub mean_pnl: f64,
+    pub pnl_std_dev: f64,
+    pub min_pnl: f64,
+    pub max_pnl: f64,
+    pub mean_hedge_error: f64,
+    pub hedge_error_std_dev: f64,
+    pub mean_costs: f64,
+    pub mean_trades: f64
+}
+
+impl PricePath {
+
+    /// Parses path from CSV lines `time,price`, where time is in years or a date `YYYY-MM-DD` (ACT/365),
+    /// both are measured from the first line. Header and empty lines are skipped.
+    pub fn from_csv(lines: &[String]) -> Result<PricePath, String> {
+        let mut path = PricePath { times: Vec::new(), prices: Vec::new() };
+        let mut start: Option<i64> = None;
+        let mut start_time: Option<f64> = None;
+        for (i, line) in lines.iter().enumerate() {
+            let fields: Vec<&str> = line.split(',').map(|f| f.trim().trim_matches('"')).collect();
+            if line.trim().is_empty() || (i == 0 && fields.len() > 1 && fields[1].parse::<f64>().is_err()) {
+                continue;
+            }
+            if fields.len() != 2 {
+                return Err(format!("line {}: expected 2 fields, found {}", i + 1, fields.len()));
+            }
+            let time = match fields[0].parse::<f64>() {
+                Ok(t) if !t.is_finite() => return Err(format!("line {}: time must be finite", i + 1)),
+                Ok(t) => t - *start_time.get_or_insert(t),
+                Err(_) => {
+                    let days = days_since_epoch(fields[0]).map_err(|e| format!("line {}: {}", i + 1, e))?;
+                    (days - *start.get_or_insert(days)) as f64 / DAYS_IN_YEAR
+                }
+            };
+            let price = fields[1].parse::<f64>().map_err(|_| format!("line {}: price is not a number: '{}'", i + 1, fields[1]))?;
+            if !price.is_finite() || price <= 0.0 {
+                return Err(format!("line {}: price must be positive and finite", i + 1));
+            }
+            if path.times.last().is_some_and(|last| time <= *last) {
+                return Err(format!("line {}: times must be increasing", i + 1));
+            }
+            path.times.push(time);
+            path.prices.push(price);
+        }
+        if path.prices.len() < 2 {
+            return Err("path must have at least 2 prices".to_string());
+        }
+        Ok(path)
+    }
+}
+
+/// Simulates geometric Brownian motion paths with equally spaced points over `horizon` years
+/// * drift - real world expected return of the underlying
+pub fn gbm_paths(spot: f64, drift: f64, vol: f64, horizon: f64, steps: usize, paths: usize, seed: u64) -> Vec<PricePath> {
+    let steps = steps.max(1);
+    let dt = horizon / steps as f64;
+    let times: Vec<f64> = (0..=steps).map(|i| i as f64 * dt).collect();
+    let mut source = PseudoRandom::new(seed);
+    let mut normals = vec![0.0; steps];
+
+    (0..paths).map(|_| {
+        source.next_normals(&mut normals);
+        let mut prices = Vec::with_capacity(steps + 1);
+        prices.push(spot);
+        for z in normals.iter() {
+            let last = prices[prices.len() - 1];
+            prices.push(last * ((drift - 0.5 * vol * vol) * dt + vol * dt.sqrt() * z).exp());
+        }
+        PricePath { times: times.clone(), prices }
+    }).collect()
+}
+
+/// Backtests delta hedging of the option position along the path. Option is traded at the start of the path at
+/// its Black/Scholes premium, its strike, rates, implied volatility and time to expiry are taken from the position
+/// (its price is replaced with the first price of the path). Cash earns risk free rate and the hedge earns dividends.
+/// Option expires at the first path point at or after its expiry, if path ends before expiry option is valued at implied volatility.
+/// Hedge is closed at the end without costs.
+pub fn backtest(position: &OptionPosition, path: &PricePath, policy: &HedgePolicy, costs: &TransactionCosts) -> Result<HedgeResult, String> {
+    if path.times.len() != path.prices.len() || path.prices.len() < 2 {
+        return Err("path must have at least 2 points with times and prices".to_string());
+    }
+    if path.times[0] != 0.0 {
+        return Err("path times must start at 0".to_string());
+    }
+    match *policy {
+        HedgePolicy::FixedInterval(0) => return Err("hedge interval must be positive".to_string()),
+        HedgePolicy::WhalleyWilmott { risk_aversion } if !(risk_aversion.is_finite() && risk_aversion > 0.0) =>
+            return Err("risk aversion must be positive".to_string()),
+        _ => ()
+    }
+    let expiry = position.bs_params.time_to_expiry;
+    let sign = if position.is_call {1.0} else {-1.0};
+    let q = position.quantity;
+    let params = |i: usize| BlackScholesParams { price: path.prices[i], time_to_expiry: expiry - path.times[i], ..position.bs_params };
+
+    let mut cash = -q * generic_black_scholes(position.is_call, &params(0));
+    let mut hedge = 0.0;
+    let mut paid = 0.0;
+    let mut trades = 0;
+    let mut i = 0;
+    loop {
+        let p = params(i);
+        // the last point is at expiry or at the end of the path
+        if p.time_to_expiry <= 0.0 || i + 1 == path.prices.len() {
+            let option = if p.time_to_expiry <= 0.0 {(sign * (p.price - p.strike)).max(0.0)} else {generic_black_scholes(position.is_call, &p)};
+            let pnl = cash + hedge * p.price + q * option;
+            return Ok(HedgeResult { pnl, hedge_error: pnl + paid, costs: paid, trades });
+        }
+
+        let delta = -q * if position.is_call {call_delta(&p)} else {put_delta(&p)};
+        let target = match *policy {
+            _ if i == 0 => Some(delta),
+            HedgePolicy::FixedInterval(n) => if i % n == 0 {Some(delta)} else {None},
+            HedgePolicy::DeltaBand(band) => if (hedge - delta).abs() > band {Some(delta)} else {None},
+            HedgePolicy::WhalleyWilmott { risk_aversion } => {
+                let position_gamma = q * gamma(&p);
+                let width = (1.5 * (-p.rate * p.time_to_expiry).exp() * costs.proportional * p.price * position_gamma.powi(2) / risk_aversion).cbrt();
+                if (hedge - delta).abs() > width {Some(delta + width * (hedge - delta).signum())} else {None}
+            }
+        };
+        if let Some(target) = target {
+            let traded = target - hedge;
+            let cost = costs.proportional * traded.abs() * p.price + costs.fixed;
+            cash -= traded * p.price + cost;
+            paid += cost;
+            hedge = target;
+            trades += 1;
+        }
+
+        // carry to the next point, dividends are paid on the hedge
+        let dt = path.times[i + 1].min(expiry) - path.times[i];
+        cash = cash * (p.rate * dt).exp() + hedge * p.price * (p.div_yield * dt).exp_m1();
+        i += 1;
+    }
+}
+
+/// Backtests delta hedging along each path and summarizes distribution of results
+pub fn backtest_paths(position: &OptionPosition, paths: &[PricePath], policy: &HedgePolicy, costs: &TransactionCosts) -> Result<HedgeSummary, String> {
+    if paths.is_empty() {
+        return Err("no paths".to_string());
+    }
+    let results = paths.iter().map(|path| backtest(position, path, policy, costs)).collect::<Result<Vec<HedgeResult>, String>>()?;
+    let n = results.len() as f64;
+    let mean = |f: &dyn Fn(&HedgeResult) -> f64| results.iter().map(f).sum::<f64>() / n;
+    let std_dev = |f: &dyn Fn(&HedgeResult) -> f64| {
+        let m = mean(f);
+        (results.iter().map(|r| (f(r) - m).powi(2)).sum::<f64>() / n).sqrt()
+    };
+    Ok(HedgeSummary {
+        mean_pnl: mean(&|r| r.pnl),
+        pnl_std_dev: std_dev(&|r| r.pnl),
+        min_pnl: results.iter().map(|r| r.pnl).fold(f64::INFINITY, f64::min),
+        max_pnl: results.iter().map(|r| r.pnl).fold(f64::NEG_INFINITY, f64::max),
+        mean_hedge_error: mean(&|r| r.hedge_error),
+        hedge_error_std_dev: std_dev(&|r| r.hedge_error),
+        mean_costs: mean(&|r| r.costs),
+        mean_trades: mean(&|r| r.trades as f64),
+        results
+    })
+}
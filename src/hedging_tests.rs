@@ -0,0 +1,89 @@
+#![cfg(test)]
+use crate::black_scholes::*;
+use crate::hedging::*;
+use crate::scenario::OptionPosition;
+
+fn position(quantity: f64) -> OptionPosition {
+    let bs_params = BlackScholesParams { price: 100.0, strike: 100.0, div_yield: 0.01, rate: 0.04, vol: 0.2, time_to_expiry: 0.25 };
+    OptionPosition { is_call: true, quantity, bs_params }
+}
+
+#[test]
+fn test_price_path_from_csv() {
+    let lines: Vec<String> = ["date,close", "2024-01-02,100.0", "2024-01-03,101.5", "", "2024-01-05,99.0"].iter().map(|l| l.to_string()).collect();
+    let path = PricePath::from_csv(&lines).unwrap();
+    assert_eq!(path.prices, vec![100.0, 101.5, 99.0]);
+    assert_eq!(path.times, vec![0.0, 1.0 / 365.0, 3.0 / 365.0]);
+
+    let years: Vec<String> = ["0,100", "0.5,104"].iter().map(|l| l.to_string()).collect();
+    assert_eq!(PricePath::from_csv(&years).unwrap().times, vec![0.0, 0.5]);
+    assert!(PricePath::from_csv(&["0,100".to_string(), "0,101".to_string()]).is_err());
+    assert!(PricePath::from_csv(&["0,100".to_string(), "0.1,-1".to_string()]).is_err());
+    assert!(PricePath::from_csv(&["0,100".to_string()]).is_err());
+
+    // numeric times are measured from the first line too
+    let shifted: Vec<String> = ["1,100", "1.25,104"].iter().map(|l| l.to_string()).collect();
+    assert_eq!(PricePath::from_csv(&shifted).unwrap().times, vec![0.0, 0.25]);
+    for line in ["0.1,inf", "0.1,nan", "inf,101", "nan,101"] {
+        assert!(PricePath::from_csv(&["0,100".to_string(), line.to_string()]).is_err(), "{}", line);
+    }
+    let late = PricePath { times: vec![1.0, 1.25], prices: vec![100.0, 104.0] };
+    assert!(backtest(&position(1.0), &late, &HedgePolicy::FixedInterval(1), &TransactionCosts::default()).is_err());
+}
+
+#[test]
+fn test_hedge_error_shrinks_with_frequency() {
+    // realized volatility equals implied, hedge error is from discrete rebalancing only
+    let paths = gbm_paths(100.0, 0.08, 0.2, 0.25, 252, 400, 3);
+    let costs = TransactionCosts::default();
+    let frequent = backtest_paths(&position(-1.0), &paths, &HedgePolicy::FixedInterval(4), &costs).unwrap();
+    let weekly = backtest_paths(&position(-1.0), &paths, &HedgePolicy::FixedInterval(20), &costs).unwrap();
+    assert!(frequent.pnl_std_dev < 0.6 * weekly.pnl_std_dev);
+    assert!(frequent.mean_pnl.abs() < 3.0 * frequent.pnl_std_dev / 20.0);
+    assert!(frequent.mean_costs == 0.0 && frequent.mean_hedge_error == frequent.mean_pnl);
+    assert_eq!(frequent.mean_trades, 63.0);
+    assert!(frequent.min_pnl <= frequent.mean_pnl && frequent.mean_pnl <= frequent.max_pnl);
+}
+
+#[test]
+fn test_realized_against_implied() {
+    // long option gains from realized volatility above implied, short option loses
+    let costs = TransactionCosts::default();
+    let paths = gbm_paths(100.0, 0.04, 0.3, 0.25, 63, 300, 5);
+    let long = backtest_paths(&position(1.0), &paths, &HedgePolicy::FixedInterval(1), &costs).unwrap();
+    let short = backtest_paths(&position(-1.0), &paths, &HedgePolicy::FixedInterval(1), &costs).unwrap();
+    assert!(long.mean_pnl > 0.0);
+    assert!((long.mean_pnl + short.mean_pnl).abs() < 1e-9);
+
+    // approximately difference of premiums at realized and implied volatility
+    let p = position(1.0).bs_params;
+    let expected = call_premium(&BlackScholesParams { vol: 0.3, ..p }) - call_premium(&p);
+    assert!((long.mean_pnl - expected).abs() < 0.25 * expected);
+}
+
+#[test]
+fn test_transaction_costs_and_bands() {
+    let paths = gbm_paths(100.0, 0.04, 0.2, 0.25, 252, 200, 9);
+    let costs = TransactionCosts { proportional: 0.001, fixed: 0.5 };
+    let position = position(-100.0);
+    let every = backtest_paths(&position, &paths, &HedgePolicy::FixedInterval(1), &costs).unwrap();
+    let band = backtest_paths(&position, &paths, &HedgePolicy::DeltaBand(5.0), &costs).unwrap();
+    let ww = backtest_paths(&position, &paths, &HedgePolicy::WhalleyWilmott { risk_aversion: 0.01 }, &costs).unwrap();
+    for summary in [&every, &band, &ww] {
+        for r in summary.results.iter() {
+            assert!(r.costs > 0.0);
+            assert!((r.pnl - (r.hedge_error - r.costs)).abs() < 1e-9);
+        }
+    }
+    assert!(band.mean_trades < 0.5 * every.mean_trades && ww.mean_trades < 0.5 * every.mean_trades);
+    assert!(band.mean_costs < every.mean_costs && ww.mean_costs < every.mean_costs);
+    assert!(ww.mean_pnl > every.mean_pnl);
+
+    // tighter band for higher risk aversion trades more often
+    let averse = backtest_paths(&position, &paths, &HedgePolicy::WhalleyWilmott { risk_aversion: 10.0 }, &costs).unwrap();
+    assert!(averse.mean_trades > ww.mean_trades);
+    assert!(backtest(&position, &paths[0], &HedgePolicy::FixedInterval(0), &costs).is_err());
+    for risk_aversion in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(backtest(&position, &paths[0], &HedgePolicy::WhalleyWilmott { risk_aversion }, &costs).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+//! module with jump-diffusion option pricing: Merton log-normal jumps and Kou double-exponential jumps.
+//! Diffusion part of the underlying is described by [`BlackScholesParams`], jumps by model specific parameters.
+use std::f64::consts::PI;
+use crate::black_scholes::*;
+use crate::math::{Complex, simpson};
+
+/// Relative weight of Poisson term below which Merton series is truncated
+const SERIES_EPS: f64 = 1e-14;
+/// Maximum number of terms in Merton series
+const MAX_TERMS: usize = 200;
+/// Number of Simpson intervals for Fourier inversion
+const FOURIER_STEPS: usize = 4000;
+
+/// Parameters of Merton jumps with normally distributed log jump sizes
+#[derive(Debug, Clone, Copy)]
+pub struct MertonJumpParams {
+    /// expected number of jumps per year
+    pub intensity: f64,
+    /// mean of log jump size
+    pub jump_mean: f64,
+    /// standard deviation of log jump size
+    pub jump_vol: f64
+}
+
+/// Parameters of Kou double exponential jumps
+#[derive(Debug, Clone, Copy)]
+pub struct KouJumpParams {
+    /// expected number of jumps per year
+    pub intensity: f64,
+    /// probability of upward jump
+    pub up_prob: f64,
+    /// rate of exponentially distributed upward log jumps (must be greater than 1)
+    pub up_rate: f64,
+    /// rate of exponentially distributed downward log jumps
+    pub down_rate: f64
+}
+
+impl KouJumpParams {
+
+    /// Kou jump parameters, upward rate must be greater than 1 for the underlying to have finite expectation
+    pub fn new(intensity: f64, up_prob: f64, up_rate: f64, down_rate: f64) -> Result<KouJumpParams, String> {
+        let jumps = KouJumpParams { intensity, up_prob, up_rate, down_rate };
+        jumps.validate()?;
+        Ok(jumps)
+    }
+
+    /// Checks that parameters define double exponential jumps with finite expected jump size
+    pub fn validate(&self) -> Result<(), String> {
+        if ![self.intensity, self.up_prob, self.up_rate, self.down_rate].iter().all(|v| v.is_finite()) {
+            return Err("Kou jump parameters must be finite".to_string());
+        }
+        if self.intensity < 0.0 {
+            return Err("jump intensity must not be negative".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.up_prob) {
+            return Err("upward jump probability must be between 0 and 1".to_string());
+        }
+        if self.up_rate <= 1.0 {
+            return Err("upward jump rate must be greater than 1".to_string());
+        }
+        if self.down_rate <= 0.0 {
+            return Err("downward jump rate must be positive".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Merton jump-diffusion premium computed as Poisson weighted series of Black/Scholes prices
+pub fn merton_premium(is_call: bool, bs_params: &BlackScholesParams, jumps: &MertonJumpParams) -> f64 {
+    let t = bs_params.time_to_expiry;
+    let k = (jumps.jump_mean + 0.5 * jumps.jump_vol * jumps.jump_vol).exp() - 1.0;
+    let lambda_t = jumps.intensity * t;
+
+    let mut premium = 0.0;
+    let mut weight = (-lambda_t).exp();
+    for n in 0..MAX_TERMS {
+        if n > 0 {
+            weight *= lambda_t / n as f64;
+        }
+        let nf = n as f64;
+        // conditionally on n jumps the price is Black/Scholes with adjusted vol and carry
+        let vol = (bs_params.vol * bs_params.vol + nf * jumps.jump_vol * jumps.jump_vol / t).sqrt();
+        let carry_adj = -jumps.intensity * k + nf * (1.0 + k).ln() / t;
+        premium += weight * generic_black_scholes(is_call, &BlackScholesParams {
+            vol,
+            div_yield: bs_params.div_yield - carry_adj,
+            ..*bs_params
+        });
+
+        if nf > lambda_t && weight < SERIES_EPS {
+            break;
+        }
+    }
+    premium
+}
+
+/// Kou double exponential jump-diffusion premium computed with Fourier inversion of characteristic function.
+/// Fails for invalid jump parameters, see [`KouJumpParams::validate`].
+pub fn kou_premium(is_call: bool, bs_params: &BlackScholesParams, jumps: &KouJumpParams) -> Result<f64, String> {
+    jumps.validate()?;
+    let jump_cf = |u: Complex| {
+        let iu = Complex::new(-u.im, u.re);
+        let up = Complex::new(jumps.up_rate, 0.0) / (Complex::new(jumps.up_rate, 0.0) - iu);
+        let down = Complex::new(jumps.down_rate, 0.0) / (Complex::new(jumps.down_rate, 0.0) + iu);
+        up.scale(jumps.up_prob) + down.scale(1.0 - jumps.up_prob)
+    };
+    Ok(fourier_premium(is_call, bs_params, jumps.intensity, jump_cf))
+}
+
+/// Black/Scholes implied volatility of Merton jump-diffusion call price
+pub fn merton_impl_vol(bs_params: &BlackScholesParams, jumps: &MertonJumpParams) -> Result<f64, f64> {
+    let premium = merton_premium(true, bs_params, jumps);
+    call_impl_vol(&premium, bs_params)
+}
+
+/// Black/Scholes implied volatility of Kou jump-diffusion call price
+pub fn kou_impl_vol(bs_params: &BlackScholesParams, jumps: &KouJumpParams) -> Result<f64, String> {
+    let premium = kou_premium(true, bs_params, jumps)?;
+    call_impl_vol(&premium, bs_params).map_err(|v| format!("implied volatility did not converge, last value is {}", v))
+}
+
+/// Premium of an option on an exponential Levy process made of Brownian motion and compound Poisson jumps,
+/// using Lewis single integral formula.
+/// * intensity - jumps intensity
+/// * jump_cf - characteristic function of log jump size
+pub(crate) fn fourier_premium<F>(is_call: bool, bs_params: &BlackScholesParams, intensity: f64, jump_cf: F) -> f64
+    where F: Fn(Complex) -> Complex {
+    let t = bs_params.time_to_expiry;
+    let var = bs_params.vol * bs_params.vol;
+    let one = Complex::new(1.0, 0.0);
+    // martingale correction makes E[exp(X_T)] = 1
+    let omega = -0.5 * var - intensity * (jump_cf(Complex::new(0.0, -1.0)).re - 1.0);
+
+    // characteristic function of X_T = ln(S_T / S) - (r - q) * T
+    let cf = |u: Complex| {
+        let iu = Complex::new(-u.im, u.re);
+        let exponent = iu.scale(omega) - (u * u).scale(0.5 * var) + (jump_cf(u) - one).scale(intensity);
+        exponent.scale(t).exp()
+    };
+
+    let k = (bs_params.price / bs_params.strike).ln() + (bs_params.rate - bs_params.div_yield) * t;
+    let integrand = |u: f64| {
+        let v = cf(Complex::new(u, -0.5)) * Complex::new(0.0, u * k).exp();
+        v.re / (u * u + 0.25)
+    };
+    let upper = (80.0 / (var * t).max(1e-4)).sqrt().max(50.0);
+    let integral = simpson(integrand, 0.0, upper, FOURIER_STEPS);
+
+    let dprice = bs_params.price * (-bs_params.div_yield * t).exp();
+    let dstrike = bs_params.strike * (-bs_params.rate * t).exp();
+    let call = dprice - (dprice * dstrike).sqrt() * integral / PI;
+    if is_call {call} else {call - dprice + dstrike}
+}
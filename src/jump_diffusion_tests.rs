@@ -0,0 +1,83 @@
+#![cfg(test)]
+use crate::black_scholes;
+use crate::black_scholes::BlackScholesParams;
+use crate::jump_diffusion::*;
+use crate::math::Complex;
+
+const EPS: f64 = 0.0001;
+
+fn bs_params(strike: f64) -> BlackScholesParams {
+    BlackScholesParams { price: 100.0, strike, rate: 0.03, div_yield: 0.01, vol: 0.2, time_to_expiry: 0.25 }
+}
+
+#[test]
+fn test_no_jumps_is_black_scholes() {
+    let merton = MertonJumpParams { intensity: 0.0, jump_mean: -0.1, jump_vol: 0.15 };
+    let kou = KouJumpParams { intensity: 0.0, up_prob: 0.4, up_rate: 10.0, down_rate: 5.0 };
+
+    for k in [80.0, 100.0, 120.0].iter() {
+        let p = bs_params(*k);
+        let call = black_scholes::call_premium(&p);
+        let put = black_scholes::put_premium(&p);
+        assert!((merton_premium(true, &p, &merton) - call).abs() < EPS);
+        assert!((merton_premium(false, &p, &merton) - put).abs() < EPS);
+        assert!((kou_premium(true, &p, &kou).unwrap() - call).abs() < EPS);
+        assert!((kou_premium(false, &p, &kou).unwrap() - put).abs() < EPS);
+    }
+}
+
+#[test]
+fn test_merton_series_matches_fourier() {
+    let jumps = MertonJumpParams { intensity: 1.5, jump_mean: -0.08, jump_vol: 0.12 };
+    let jump_cf = |u: Complex| {
+        let iu = Complex::new(-u.im, u.re);
+        (iu.scale(jumps.jump_mean) - (u * u).scale(0.5 * jumps.jump_vol * jumps.jump_vol)).exp()
+    };
+
+    for k in [85.0, 100.0, 115.0].iter() {
+        let p = bs_params(*k);
+        let series = merton_premium(true, &p, &jumps);
+        let fourier = fourier_premium(true, &p, jumps.intensity, jump_cf);
+        assert!((series - fourier).abs() < EPS, "strike {}: {} vs {}", k, series, fourier);
+    }
+}
+
+#[test]
+fn test_kou_put_call_parity() {
+    let jumps = KouJumpParams { intensity: 3.0, up_prob: 0.3, up_rate: 25.0, down_rate: 10.0 };
+    let p = bs_params(95.0);
+    let call = kou_premium(true, &p, &jumps).unwrap();
+    let put = kou_premium(false, &p, &jumps).unwrap();
+    assert!((black_scholes::callput_price(true, &call, &p) - put).abs() < EPS);
+    assert!(call > black_scholes::call_premium(&p));
+}
+
+#[test]
+fn test_jumps_produce_skew() {
+    let merton = MertonJumpParams { intensity: 1.0, jump_mean: -0.15, jump_vol: 0.1 };
+    let kou = KouJumpParams { intensity: 2.0, up_prob: 0.2, up_rate: 30.0, down_rate: 8.0 };
+
+    let merton_low = merton_impl_vol(&bs_params(90.0), &merton).unwrap();
+    let merton_high = merton_impl_vol(&bs_params(110.0), &merton).unwrap();
+    assert!(merton_low > merton_high);
+    assert!(merton_high > 0.2);
+
+    let kou_low = kou_impl_vol(&bs_params(90.0), &kou).unwrap();
+    let kou_high = kou_impl_vol(&bs_params(110.0), &kou).unwrap();
+    assert!(kou_low > kou_high);
+}
+
+#[test]
+fn test_invalid_kou_params() {
+    assert!(KouJumpParams::new(2.0, 0.2, 30.0, 8.0).is_ok());
+    // jump compensator p * up_rate / (up_rate - 1) is undefined for up_rate <= 1
+    for up_rate in [1.0, 0.5, f64::NAN] {
+        assert!(KouJumpParams::new(2.0, 0.2, up_rate, 8.0).is_err());
+        let jumps = KouJumpParams { intensity: 2.0, up_prob: 0.2, up_rate, down_rate: 8.0 };
+        assert!(kou_premium(true, &bs_params(100.0), &jumps).is_err());
+        assert!(kou_impl_vol(&bs_params(100.0), &jumps).is_err());
+    }
+    assert!(KouJumpParams::new(-1.0, 0.2, 30.0, 8.0).is_err());
+    assert!(KouJumpParams::new(2.0, 1.5, 30.0, 8.0).is_err());
+    assert!(KouJumpParams::new(2.0, 0.2, 30.0, 0.0).is_err());
+}
@@ -46,4 +46,51 @@
 //! ```
 
 pub mod black_scholes;
-mod black_scholes_tests;
\ No newline at end of file
+mod black_scholes_tests;
+pub mod math;
+pub mod local_vol;
+mod local_vol_tests;
+pub mod jump_diffusion;
+mod jump_diffusion_tests;
+pub mod monte_carlo;
+mod monte_carlo_tests;
+pub mod sobol;
+mod sobol_tests;
+pub mod finite_difference;
+mod finite_difference_tests;
+pub mod digital;
+mod digital_tests;
+pub mod barrier;
+mod barrier_tests;
+pub mod asian;
+mod asian_tests;
+pub mod lookback;
+mod lookback_tests;
+pub mod forward_start;
+mod forward_start_tests;
+pub mod multi_asset;
+mod multi_asset_tests;
+pub mod compound;
+mod compound_tests;
+pub mod quanto;
+mod quanto_tests;
+pub mod variance_swap;
+mod variance_swap_tests;
+pub mod vol_index;
+mod vol_index_tests;
+pub mod density;
+mod density_tests;
+pub mod chain;
+mod chain_tests;
+pub mod scenario;
+mod scenario_tests;
+pub mod portfolio;
+mod portfolio_tests;
+pub mod pnl_explain;
+mod pnl_explain_tests;
+pub mod var;
+mod var_tests;
+pub mod hedging;
+mod hedging_tests;
+pub mod realized_vol;
+mod realized_vol_tests;
\ No newline at end of file
@@ -0,0 +1,171 @@
+//! module with Dupire local volatility construction from implied volatility surface and local volatility Monte Carlo pricer.
+use rand::prelude::*;
+use rv::prelude::*;
+use crate::black_scholes::BlackScholesParams;
+use crate::math::CubicSpline;
+use crate::monte_carlo::McResult;
+
+/// Lower bound for local variance, applied when surface has calendar or butterfly arbitrage
+const MIN_LOCAL_VAR: f64 = 0.0001;
+/// Upper bound for local variance
+const MAX_LOCAL_VAR: f64 = 25.0;
+/// Lower bound for Dupire formula denominator (butterfly arbitrage safeguard)
+const MIN_DENOM: f64 = 0.01;
+
+/// Implied volatility surface stored in total variance form `w(T, y) = vol^2 * T`
+/// where `y = ln(K / F(T))` is log-moneyness against the forward.
+/// Each expiry slice is interpolated with natural cubic spline in log-moneyness,
+/// total variance is interpolated linearly between expiries.
+#[derive(Debug, Clone)]
+pub struct ImpliedVolSurface {
+    /// spot price of the underlying asset
+    pub price: f64,
+    /// risk free rate
+    pub rate: f64,
+    /// annual dividend yield
+    pub div_yield: f64,
+    expiries: Vec<f64>,
+    slices: Vec<CubicSpline>
+}
+
+impl ImpliedVolSurface {
+
+    /// Builds surface from implied volatility quotes
+    /// * expiries - increasing times to expiry in years
+    /// * strikes - increasing strikes shared by all expiries
+    /// * vols - implied volatilities, `vols[i][j]` corresponds to `expiries[i]` and `strikes[j]`
+    pub fn new(price: f64, rate: f64, div_yield: f64, expiries: &[f64], strikes: &[f64], vols: &[Vec<f64>]) -> Result<ImpliedVolSurface, String> {
+        if expiries.is_empty() || expiries.len() != vols.len() {
+            return Err("each expiry must have a row of volatilities".to_string());
+        }
+        if expiries[0] <= 0.0 || expiries.windows(2).any(|w| w[1] <= w[0]) {
+            return Err("expiries must be positive and strictly increasing".to_string());
+        }
+        if strikes.iter().any(|k| *k <= 0.0) {
+            return Err("strikes must be positive".to_string());
+        }
+
+        let mut slices = Vec::with_capacity(expiries.len());
+        for (t, row) in expiries.iter().zip(vols.iter()) {
+            if row.len() != strikes.len() {
+                return Err(format!("volatilities row for expiry {} has wrong length", t));
+            }
+            let fwd = price * ((rate - div_yield) * t).exp();
+            let ys: Vec<f64> = strikes.iter().map(|k| (k / fwd).ln()).collect();
+            let ws: Vec<f64> = row.iter().map(|v| v * v * t).collect();
+            slices.push(CubicSpline::new(&ys, &ws)?);
+        }
+
+        Ok(ImpliedVolSurface { price, rate, div_yield, expiries: expiries.to_vec(), slices })
+    }
+
+    /// Forward price of the underlying for time `t`
+    pub fn forward(&self, t: f64) -> f64 {
+        self.price * ((self.rate - self.div_yield) * t).exp()
+    }
+
+    /// Total implied variance with its derivatives `(w, dw/dT, dw/dy, d2w/dy2)` at time `t` and log-moneyness `y`
+    pub fn total_variance(&self, t: f64, y: f64) -> (f64, f64, f64, f64) {
+        let n = self.expiries.len();
+        let (i, scale) = if t <= self.expiries[0] {
+            (0, t / self.expiries[0])
+        } else if t >= self.expiries[n - 1] {
+            (n - 1, t / self.expiries[n - 1])
+        } else {
+            let i = self.expiries.iter().position(|e| *e > t).unwrap() - 1;
+            let (t0, t1) = (self.expiries[i], self.expiries[i + 1]);
+            let (w0, dw0, ddw0) = self.slices[i].eval(y);
+            let (w1, dw1, ddw1) = self.slices[i + 1].eval(y);
+            let a = (t - t0) / (t1 - t0);
+            return (w0 + a * (w1 - w0), (w1 - w0) / (t1 - t0), dw0 + a * (dw1 - dw0), ddw0 + a * (ddw1 - ddw0));
+        };
+
+        // flat implied volatility extrapolation in time
+        let (w, dw, ddw) = self.slices[i].eval(y);
+        (w * scale, w / self.expiries[i], dw * scale, ddw * scale)
+    }
+
+    /// Implied volatility for the given strike and time to expiry
+    pub fn implied_vol(&self, strike: f64, t: f64) -> f64 {
+        let y = (strike / self.forward(t)).ln();
+        (self.total_variance(t, y).0 / t).sqrt()
+    }
+
+    /// Black/Scholes parameters of vanilla option on the surface
+    pub fn bs_params(&self, strike: f64, time_to_expiry: f64) -> BlackScholesParams {
+        BlackScholesParams {
+            price: self.price,
+            strike,
+            rate: self.rate,
+            div_yield: self.div_yield,
+            vol: self.implied_vol(strike, time_to_expiry),
+            time_to_expiry
+        }
+    }
+}
+
+/// Dupire local volatility at time `t` and underlying level `spot`, computed from total implied variance
+/// (see Gatheral, "The Volatility Surface", eq. 1.10).
+/// Negative calendar spread and negative butterfly terms are floored, so that local variance stays in
+/// `[MIN_LOCAL_VAR, MAX_LOCAL_VAR]`.
+pub fn local_vol(surface: &ImpliedVolSurface, spot: f64, t: f64) -> f64 {
+    let y = (spot / surface.forward(t)).ln();
+    let (w, dw_dt, dw_dy, d2w_dy2) = surface.total_variance(t, y);
+    if w <= 0.0 {
+        return MIN_LOCAL_VAR.sqrt();
+    }
+
+    let denom = 1.0 - y / w * dw_dy
+        + 0.25 * (-0.25 - 1.0 / w + y * y / (w * w)) * dw_dy * dw_dy
+        + 0.5 * d2w_dy2;
+    let local_var = dw_dt.max(0.0) / denom.max(MIN_DENOM);
+
+    local_var.clamp(MIN_LOCAL_VAR, MAX_LOCAL_VAR).sqrt()
+}
+
+/// Parameters of local volatility Monte Carlo simulation
+#[derive(Debug, Clone, Copy)]
+pub struct LocalVolMcParams {
+    /// number of simulated paths (antithetic pairs are counted as two paths)
+    pub paths: usize,
+    /// number of time steps per path
+    pub steps: usize,
+    /// seed of random numbers generator
+    pub seed: u64
+}
+
+/// Prices european option under Dupire local volatility with Monte Carlo simulation using log-Euler scheme
+/// and antithetic variates.
+pub fn local_vol_mc_premium(is_call: bool, surface: &ImpliedVolSurface, strike: f64, time_to_expiry: f64, mc_params: &LocalVolMcParams) -> McResult {
+    let sign = if is_call {1.0} else {-1.0};
+    let n: Gaussian = Gaussian::standard();
+    let mut rng = StdRng::seed_from_u64(mc_params.seed);
+    let dt = time_to_expiry / mc_params.steps as f64;
+    let sqrt_dt = dt.sqrt();
+    let drift = (surface.rate - surface.div_yield) * dt;
+    let discount = (-surface.rate * time_to_expiry).exp();
+
+    let pairs = (mc_params.paths / 2).max(1);
+    let mut sum = 0.0;
+    let mut sum_sq = 0.0;
+    for _ in 0..pairs {
+        let mut s_up = surface.price;
+        let mut s_down = surface.price;
+        for i in 0..mc_params.steps {
+            let t = (i as f64 + 0.5) * dt;
+            let z: f64 = n.draw(&mut rng);
+            let v_up = local_vol(surface, s_up, t);
+            let v_down = local_vol(surface, s_down, t);
+            s_up *= (drift - 0.5 * v_up * v_up * dt + v_up * sqrt_dt * z).exp();
+            s_down *= (drift - 0.5 * v_down * v_down * dt - v_down * sqrt_dt * z).exp();
+        }
+        let payoff = 0.5 * ((sign * (s_up - strike)).max(0.0) + (sign * (s_down - strike)).max(0.0));
+        sum += payoff;
+        sum_sq += payoff * payoff;
+    }
+
+    let count = pairs as f64;
+    let mean = sum / count;
+    let variance = (sum_sq / count - mean * mean).max(0.0);
+    McResult { premium: discount * mean, std_error: discount * (variance / count).sqrt() }
+}
@@ -0,0 +1,71 @@
+#![cfg(test)]
+use crate::black_scholes;
+use crate::local_vol::*;
+
+const STRIKES: [f64; 9] = [60.0, 70.0, 80.0, 90.0, 100.0, 110.0, 120.0, 130.0, 140.0];
+const EXPIRIES: [f64; 3] = [0.25, 0.5, 1.0];
+
+/// surface with downward sloping smile
+fn skew_surface() -> ImpliedVolSurface {
+    let vols: Vec<Vec<f64>> = EXPIRIES.iter()
+        .map(|t| STRIKES.iter().map(|k| 0.2 - 0.15 * (k / 100.0_f64).ln() / t.sqrt().sqrt()).collect())
+        .collect();
+    ImpliedVolSurface::new(100.0, 0.03, 0.01, &EXPIRIES, &STRIKES, &vols).unwrap()
+}
+
+#[test]
+fn test_surface_reproduces_quotes() {
+    let surface = skew_surface();
+    for t in EXPIRIES.iter() {
+        let k = 90.0;
+        let expected = 0.2 - 0.15 * (k / 100.0_f64).ln() / t.sqrt().sqrt();
+        // quoted strikes fall on spline knots, so quotes are reproduced exactly
+        assert!((surface.implied_vol(k, *t) - expected).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn test_flat_surface_local_vol() {
+    let vols = vec![vec![0.25; STRIKES.len()]; EXPIRIES.len()];
+    let surface = ImpliedVolSurface::new(100.0, 0.02, 0.0, &EXPIRIES, &STRIKES, &vols).unwrap();
+
+    for spot in [70.0, 100.0, 130.0].iter() {
+        for t in [0.1, 0.3, 0.75, 2.0].iter() {
+            assert!((local_vol(&surface, *spot, *t) - 0.25).abs() < 1e-10);
+        }
+    }
+}
+
+#[test]
+fn test_negative_local_var_is_floored() {
+    // total variance decreases in time: calendar arbitrage
+    let vols = vec![vec![0.4; STRIKES.len()], vec![0.1; STRIKES.len()], vec![0.1; STRIKES.len()]];
+    let surface = ImpliedVolSurface::new(100.0, 0.02, 0.0, &EXPIRIES, &STRIKES, &vols).unwrap();
+
+    let lv = local_vol(&surface, 100.0, 0.3);
+    assert!(lv.is_finite() && lv > 0.0);
+}
+
+#[test]
+fn test_invalid_surface() {
+    let vols = vec![vec![0.2; 2]];
+    assert!(ImpliedVolSurface::new(100.0, 0.0, 0.0, &[1.0], &[90.0, 110.0, 120.0], &vols).is_err());
+    assert!(ImpliedVolSurface::new(100.0, 0.0, 0.0, &[1.0, 0.5], &[90.0, 110.0], &[vols[0].clone(), vols[0].clone()]).is_err());
+}
+
+#[test]
+fn test_local_vol_mc_reproduces_vanillas() {
+    let surface = skew_surface();
+    let mc_params = LocalVolMcParams { paths: 20000, steps: 50, seed: 42 };
+
+    for k in [90.0, 100.0, 110.0].iter() {
+        let bs_params = surface.bs_params(*k, 0.5);
+        let call = black_scholes::call_premium(&bs_params);
+        let mc_call = local_vol_mc_premium(true, &surface, *k, 0.5, &mc_params);
+        assert!((mc_call.premium - call).abs() < 3.0 * mc_call.std_error + 0.01 * call, "strike {}: {:?} vs {}", k, mc_call, call);
+
+        let put = black_scholes::put_premium(&bs_params);
+        let mc_put = local_vol_mc_premium(false, &surface, *k, 0.5, &mc_params);
+        assert!((mc_put.premium - put).abs() < 3.0 * mc_put.std_error + 0.01 * put, "strike {}: {:?} vs {}", k, mc_put, put);
+    }
+}
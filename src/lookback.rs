@@ -0,0 +1,50 @@
+//! module with lookback options pricing: floating strike (Goldman, Sosin and Gatto) and
+//! fixed strike (Conze and Viswanathan) options with continuously monitored extremum.
+use rv::prelude::*;
+use crate::black_scholes::*;
+
+/// Cost of carry below which it is treated as zero
+const SMALL_CARRY: f64 = 1e-8;
+
+/// Floating strike lookback premium. Call pays `S_T - min(S)`, put pays `max(S) - S_T`.
+/// Strike of `bs_params` is not used.
+/// * extremum - minimum (for call) or maximum (for put) of the underlying price observed so far
+pub fn floating_lookback_premium(is_call: bool, bs_params: &BlackScholesParams, extremum: f64) -> f64 {
+    let vanilla = generic_black_scholes(is_call, &BlackScholesParams { strike: extremum, ..*bs_params });
+    vanilla + extremum_premium(!is_call, bs_params, extremum)
+}
+
+/// Fixed strike lookback premium. Call pays `max(max(S) - K, 0)`, put pays `max(K - min(S), 0)`.
+/// * extremum - maximum (for call) or minimum (for put) of the underlying price observed so far
+pub fn fixed_lookback_premium(is_call: bool, bs_params: &BlackScholesParams, extremum: f64) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let strike = bs_params.strike;
+    if sign * (strike - extremum) > 0.0 {
+        generic_black_scholes(is_call, bs_params) + extremum_premium(is_call, bs_params, strike)
+    } else {
+        // intrinsic value is locked in, the rest is option on the new extremum
+        let locked = (-bs_params.rate * bs_params.time_to_expiry).exp() * sign * (extremum - strike);
+        locked + generic_black_scholes(is_call, &BlackScholesParams { strike: extremum, ..*bs_params })
+            + extremum_premium(is_call, bs_params, extremum)
+    }
+}
+
+/// Value of the option on the future extremum over the vanilla option with strike `level`
+/// (running maximum if `is_max`, running minimum otherwise)
+fn extremum_premium(is_max: bool, bs_params: &BlackScholesParams, level: f64) -> f64 {
+    let sign = if is_max {1.0} else {-1.0};
+    let n: Gaussian = Gaussian::standard();
+    let s = bs_params.price;
+    let t = bs_params.time_to_expiry;
+    let var = bs_params.vol * bs_params.vol;
+    let mut b = bs_params.rate - bs_params.div_yield;
+    if b.abs() < SMALL_CARRY {
+        b = SMALL_CARRY;
+    }
+    let vt = bs_params.vol * t.sqrt();
+    let d = ((s / level).ln() + (b + var / 2.0) * t) / vt;
+
+    s * (-bs_params.rate * t).exp() * var / (2.0 * b) * sign * (
+        -(s / level).powf(-2.0 * b / var) * n.cdf(&(sign * (d - 2.0 * b * t.sqrt() / bs_params.vol)))
+        + (b * t).exp() * n.cdf(&(sign * d)))
+}
@@ -0,0 +1,48 @@
+#![cfg(test)]
+use crate::black_scholes::BlackScholesParams;
+use crate::lookback::*;
+use crate::monte_carlo::*;
+
+const EPS: f64 = 0.0001;
+
+#[test]
+fn test_floating_lookback_reference_value() {
+    // Haug's example: floating strike lookback call
+    let p = BlackScholesParams { price: 120.0, strike: 0.0, rate: 0.1, div_yield: 0.06, vol: 0.3, time_to_expiry: 0.5 };
+    assert!((floating_lookback_premium(true, &p, 100.0) - 25.3533).abs() < 0.001);
+}
+
+#[test]
+fn test_fixed_floating_symmetry() {
+    let p = BlackScholesParams { price: 100.0, strike: 95.0, rate: 0.05, div_yield: 0.02, vol: 0.25, time_to_expiry: 1.0 };
+    let dprice = p.price * (-p.div_yield * p.time_to_expiry).exp();
+    let dstrike = p.strike * (-p.rate * p.time_to_expiry).exp();
+
+    // strike below running maximum: max - K = (max - S_T) + (S_T - K)
+    let fixed_call = fixed_lookback_premium(true, &p, 105.0);
+    let floating_put = floating_lookback_premium(false, &p, 105.0);
+    assert!((fixed_call - floating_put - dprice + dstrike).abs() < EPS);
+
+    // strike above running minimum: K - min = (S_T - min) + (K - S_T)
+    let fixed_put = fixed_lookback_premium(false, &p, 90.0);
+    let floating_call = floating_lookback_premium(true, &p, 90.0);
+    assert!((fixed_put - floating_call - dstrike + dprice).abs() < EPS);
+}
+
+#[test]
+fn test_lookback_against_monte_carlo() {
+    // zero carry exercises the small carry limit
+    let p = BlackScholesParams { price: 100.0, strike: 110.0, rate: 0.03, div_yield: 0.03, vol: 0.2, time_to_expiry: 0.5 };
+    let steps = 200;
+    let mc_params = McParams { paths: 10000, steps, seed: 9, antithetic: true, control_variate: false };
+    // discretely observed extremum is shifted to continuous one (Broadie, Glasserman and Kou)
+    let shift = (0.5826 * p.vol * (p.time_to_expiry / steps as f64).sqrt()).exp();
+
+    let mc = mc_premium(&p, &mc_params, |path| (shift * path.iter().cloned().fold(100.0, f64::max) - 110.0).max(0.0));
+    let fixed_call = fixed_lookback_premium(true, &p, 100.0);
+    assert!((fixed_call - mc.premium).abs() < 3.0 * mc.std_error + 0.05, "{} vs {:?}", fixed_call, mc);
+
+    let mc = mc_premium(&p, &mc_params, |path| path[steps] - path.iter().cloned().fold(100.0, f64::min) / shift);
+    let floating_call = floating_lookback_premium(true, &p, 100.0);
+    assert!((floating_call - mc.premium).abs() < 3.0 * mc.std_error + 0.05, "{} vs {:?}", floating_call, mc);
+}
@@ -0,0 +1,282 @@
+//! module with numerical helpers shared by pricing modules.
+use std::f64::consts::PI;
+use rv::prelude::*;
+
+/// Natural cubic spline through a set of points with strictly increasing abscissas.
+/// Outside of the knots range the spline is extrapolated flat.
+#[derive(Debug, Clone)]
+pub struct CubicSpline {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    /// second derivatives at knots
+    m: Vec<f64>
+}
+
+impl CubicSpline {
+
+    /// Builds natural cubic spline. Returns error if there are less than two points,
+    /// lengths do not match or abscissas are not strictly increasing.
+    pub fn new(xs: &[f64], ys: &[f64]) -> Result<CubicSpline, String> {
+        if xs.len() != ys.len() {
+            return Err("abscissas and ordinates must have the same length".to_string());
+        }
+        if xs.len() < 2 {
+            return Err("at least two points are required for spline".to_string());
+        }
+        if xs.windows(2).any(|w| w[1] <= w[0]) {
+            return Err("abscissas must be strictly increasing".to_string());
+        }
+
+        // tridiagonal system for second derivatives (Thomas algorithm)
+        let n = xs.len();
+        let mut m = vec![0.0; n];
+        let mut c = vec![0.0; n];
+        let mut d = vec![0.0; n];
+        for i in 1..n - 1 {
+            let h0 = xs[i] - xs[i - 1];
+            let h1 = xs[i + 1] - xs[i];
+            let rhs = 6.0 * ((ys[i + 1] - ys[i]) / h1 - (ys[i] - ys[i - 1]) / h0);
+            let denom = 2.0 * (h0 + h1) - h0 * c[i - 1];
+            c[i] = h1 / denom;
+            d[i] = (rhs - h0 * d[i - 1]) / denom;
+        }
+        for i in (1..n - 1).rev() {
+            m[i] = d[i] - c[i] * m[i + 1];
+        }
+
+        Ok(CubicSpline { xs: xs.to_vec(), ys: ys.to_vec(), m })
+    }
+
+    /// Spline value at `x`
+    pub fn value(&self, x: f64) -> f64 {
+        self.eval(x).0
+    }
+
+    /// Spline value together with its first and second derivatives at `x`
+    pub fn eval(&self, x: f64) -> (f64, f64, f64) {
+        let n = self.xs.len();
+        if x <= self.xs[0] {
+            return (self.ys[0], 0.0, 0.0);
+        }
+        if x >= self.xs[n - 1] {
+            return (self.ys[n - 1], 0.0, 0.0);
+        }
+
+        let i = match self.xs.binary_search_by(|v| v.partial_cmp(&x).unwrap()) {
+            Ok(i) => i.min(n - 2),
+            Err(i) => i - 1
+        };
+        let h = self.xs[i + 1] - self.xs[i];
+        let a = (self.xs[i + 1] - x) / h;
+        let b = (x - self.xs[i]) / h;
+        let (m0, m1) = (self.m[i], self.m[i + 1]);
+
+        let value = a * self.ys[i] + b * self.ys[i + 1] + ((a.powi(3) - a) * m0 + (b.powi(3) - b) * m1) * h * h / 6.0;
+        let first = (self.ys[i + 1] - self.ys[i]) / h - (3.0 * a * a - 1.0) * h * m0 / 6.0 + (3.0 * b * b - 1.0) * h * m1 / 6.0;
+        let second = a * m0 + b * m1;
+        (value, first, second)
+    }
+}
+
+/// Linear interpolation over sorted abscissas with flat extrapolation
+pub fn lin_interp(xs: &[f64], ys: &[f64], x: f64) -> f64 {
+    let n = xs.len();
+    if x <= xs[0] {
+        return ys[0];
+    }
+    if x >= xs[n - 1] {
+        return ys[n - 1];
+    }
+    let i = xs.iter().position(|v| *v > x).unwrap() - 1;
+    let w = (x - xs[i]) / (xs[i + 1] - xs[i]);
+    ys[i] + w * (ys[i + 1] - ys[i])
+}
+
+/// Minimal complex number used by characteristic function pricers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Complex {
+    pub re: f64,
+    pub im: f64
+}
+
+impl Complex {
+
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    pub fn exp(self) -> Complex {
+        let r = self.re.exp();
+        Complex::new(r * self.im.cos(), r * self.im.sin())
+    }
+
+    pub fn scale(self, k: f64) -> Complex {
+        Complex::new(self.re * k, self.im * k)
+    }
+}
+
+impl std::ops::Add for Complex {
+    type Output = Complex;
+    fn add(self, o: Complex) -> Complex {
+        Complex::new(self.re + o.re, self.im + o.im)
+    }
+}
+
+impl std::ops::Sub for Complex {
+    type Output = Complex;
+    fn sub(self, o: Complex) -> Complex {
+        Complex::new(self.re - o.re, self.im - o.im)
+    }
+}
+
+impl std::ops::Mul for Complex {
+    type Output = Complex;
+    fn mul(self, o: Complex) -> Complex {
+        Complex::new(self.re * o.re - self.im * o.im, self.re * o.im + self.im * o.re)
+    }
+}
+
+impl std::ops::Div for Complex {
+    type Output = Complex;
+    fn div(self, o: Complex) -> Complex {
+        let d = o.re * o.re + o.im * o.im;
+        Complex::new((self.re * o.re + self.im * o.im) / d, (self.im * o.re - self.re * o.im) / d)
+    }
+}
+
+/// Composite Simpson integration of `f` over `[a, b]` with `n` (rounded up to even) intervals
+pub fn simpson<F>(f: F, a: f64, b: f64, n: usize) -> f64
+    where F: Fn(f64) -> f64 {
+    let n = n + n % 2;
+    let h = (b - a) / n as f64;
+    let mut sum = f(a) + f(b);
+    for i in 1..n {
+        let w = if i % 2 == 1 {4.0} else {2.0};
+        sum += w * f(a + i as f64 * h);
+    }
+    sum * h / 3.0
+}
+
+/// Gauss-Legendre nodes (negative half) and weights for 6, 12 and 20 points
+const GL_X: [&[f64]; 3] = [
+    &[-0.932469514203152, -0.6612093864662646, -0.2386191860831969],
+    &[-0.9815606342467192, -0.9041172563704748, -0.7699026741943047, -0.5873179542866175, -0.3678314989981802, -0.1252334085114689],
+    &[-0.9931285991850949, -0.9639719272779138, -0.912234428251326, -0.8391169718222189, -0.7463319064601508,
+      -0.636053680726515, -0.5108670019508271, -0.3737060887154195, -0.2277858511416451, -0.0765265211334973]
+];
+const GL_W: [&[f64]; 3] = [
+    &[0.1713244923791705, 0.3607615730481386, 0.467913934572691],
+    &[0.0471753363865118, 0.1069393259953186, 0.1600783285433463, 0.2031674267230658, 0.2334925365383548, 0.2491470458134029],
+    &[0.0176140071391523, 0.0406014298003870, 0.0626720483341090, 0.0832767415767047, 0.1019301198172405,
+      0.1181945319615183, 0.1316886384491765, 0.1420961093183822, 0.1491729864726038, 0.152753387130726]
+];
+
+/// Bivariate standard normal distribution function `P(X < x, Y < y)` with correlation `rho`
+/// (Genz's algorithm, double precision accuracy)
+pub fn bivariate_normal_cdf(x: f64, y: f64, rho: f64) -> f64 {
+    let n: Gaussian = Gaussian::standard();
+    let two_pi = 2.0 * PI;
+    let (h, mut k) = (-x, -y);
+    let mut hk = h * k;
+    let g = if rho.abs() < 0.3 {0} else if rho.abs() < 0.75 {1} else {2};
+    let (xs, ws) = (GL_X[g], GL_W[g]);
+
+    if rho.abs() < 0.925 {
+        let hs = (h * h + k * k) / 2.0;
+        let asr = rho.asin();
+        let mut bvn = 0.0;
+        for (xi, wi) in xs.iter().zip(ws.iter()) {
+            for sign in [-1.0, 1.0].iter() {
+                let sn = (asr * (sign * xi + 1.0) / 2.0).sin();
+                bvn += wi * ((sn * hk - hs) / (1.0 - sn * sn)).exp();
+            }
+        }
+        return bvn * asr / (2.0 * two_pi) + n.cdf(&-h) * n.cdf(&-k);
+    }
+
+    if rho < 0.0 {
+        k = -k;
+        hk = -hk;
+    }
+    let mut bvn = 0.0;
+    if rho.abs() < 1.0 {
+        let a2 = (1.0 - rho) * (1.0 + rho);
+        let mut a = a2.sqrt();
+        let bs = (h - k) * (h - k);
+        let c = (4.0 - hk) / 8.0;
+        let d = (12.0 - hk) / 16.0;
+        bvn = a * (-(bs / a2 + hk) / 2.0).exp() * (1.0 - c * (bs - a2) * (1.0 - d * bs / 5.0) / 3.0 + c * d * a2 * a2 / 5.0);
+        if hk > -160.0 {
+            let b = bs.sqrt();
+            bvn -= (-hk / 2.0).exp() * two_pi.sqrt() * n.cdf(&(-b / a)) * b * (1.0 - c * bs * (1.0 - d * bs / 5.0) / 3.0);
+        }
+        a /= 2.0;
+        for (xi, wi) in xs.iter().zip(ws.iter()) {
+            for sign in [-1.0, 1.0].iter() {
+                let xs2 = (a * (sign * xi + 1.0)).powi(2);
+                let rs = (1.0 - xs2).sqrt();
+                let asr = -(bs / xs2 + hk) / 2.0;
+                if asr > -100.0 {
+                    bvn += a * wi * asr.exp() * ((-hk * xs2 / (2.0 * (1.0 + rs).powi(2))).exp() / rs - (1.0 + c * xs2 * (1.0 + d * xs2)));
+                }
+            }
+        }
+        bvn = -bvn / two_pi;
+    }
+
+    if rho > 0.0 {
+        bvn + n.cdf(&-h.max(k))
+    } else {
+        -bvn + (n.cdf(&-h) - n.cdf(&-k)).max(0.0)
+    }
+}
+
+/// Cholesky decomposition `L * L^T` of symmetric positive definite matrix, returns lower triangular `L`
+/// or `None` if matrix is not positive definite
+pub fn cholesky(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut l = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+            if i == j {
+                let d = matrix[i][i] - sum;
+                if d <= 0.0 {
+                    return None;
+                }
+                l[i][i] = d.sqrt();
+            } else {
+                l[i][j] = (matrix[i][j] - sum) / l[j][j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Root of continuous function `f` on interval `[a, b]` with sign change found by bisection to absolute `tolerance`,
+/// `None` if `f` has the same sign at both ends
+pub fn bisection<F>(f: F, a: f64, b: f64, tolerance: f64) -> Option<f64>
+    where F: Fn(f64) -> f64 {
+    let (mut lo, mut hi) = (a, b);
+    let mut f_lo = f(lo);
+    if f_lo == 0.0 {
+        return Some(lo);
+    }
+    if f_lo * f(hi) > 0.0 {
+        return None;
+    }
+    while (hi - lo).abs() > tolerance {
+        let mid = 0.5 * (lo + hi);
+        let f_mid = f(mid);
+        if f_mid == 0.0 || mid == lo || mid == hi {
+            return Some(mid);
+        }
+        if (f_mid < 0.0) == (f_lo < 0.0) {
+            lo = mid;
+            f_lo = f_mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(0.5 * (lo + hi))
+}
@@ -0,0 +1,146 @@
+//! module with Monte Carlo pricing engine simulating geometric Brownian motion paths of the option underlying.
+//!
+//! # Example
+//! ```
+//! use ivol::black_scholes::*;
+//! use ivol::monte_carlo::*;
+//!
+//! let bs_params = BlackScholesParams{price: 100.0, strike: 100.0, div_yield: 0.0, rate: 0.03, vol: 0.25, time_to_expiry: 1.0};
+//! let mc_params = McParams{paths: 10000, steps: 12, seed: 7, antithetic: true, control_variate: true};
+//!
+//! // call on the average of monthly fixings, priced with vanilla call as control variate
+//! let asian = mc_premium(&bs_params, &mc_params, |path| {
+//!     let avg = path[1..].iter().sum::<f64>() / (path.len() - 1) as f64;
+//!     (avg - 100.0).max(0.0)
+//! });
+//! assert!(asian.premium < call_premium(&bs_params));
+//! ```
+use rand::prelude::*;
+use rv::prelude::*;
+use crate::black_scholes::*;
+
+/// Source of independent standard normal variates for path construction
+pub trait RandomSource {
+    /// Fills `out` with normal variates for one path, `out.len()` is the number of time steps
+    fn next_normals(&mut self, out: &mut [f64]);
+
+    /// Number of normals per path the source is built for, `None` if it fills any number
+    fn dimension(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Pseudo random source based on seedable standard generator
+pub struct PseudoRandom {
+    rng: StdRng,
+    n: Gaussian
+}
+
+impl PseudoRandom {
+    pub fn new(seed: u64) -> PseudoRandom {
+        PseudoRandom { rng: StdRng::seed_from_u64(seed), n: Gaussian::standard() }
+    }
+}
+
+impl RandomSource for PseudoRandom {
+    fn next_normals(&mut self, out: &mut [f64]) {
+        for z in out.iter_mut() {
+            *z = self.n.draw(&mut self.rng);
+        }
+    }
+}
+
+/// Parameters of Monte Carlo simulation
+#[derive(Debug, Clone, Copy)]
+pub struct McParams {
+    /// number of simulated paths (with antithetic variates each pair counts as two paths)
+    pub paths: usize,
+    /// number of equally spaced time steps per path
+    pub steps: usize,
+    /// seed of random numbers generator
+    pub seed: u64,
+    /// use antithetic variates
+    pub antithetic: bool,
+    /// use vanilla option on the same strike, priced with [`call_premium`], as control variate
+    pub control_variate: bool
+}
+
+/// Result of Monte Carlo pricing
+#[derive(Debug, Clone, Copy)]
+pub struct McResult {
+    /// discounted expected payoff
+    pub premium: f64,
+    /// standard error of the premium estimate
+    pub std_error: f64
+}
+
+/// Prices path dependent payoff with pseudo random numbers seeded from `mc_params.seed`.
+/// Payoff function receives underlying prices at times `0, dt, ..., T`, so the first element is always spot price.
+pub fn mc_premium<F>(bs_params: &BlackScholesParams, mc_params: &McParams, payoff: F) -> McResult
+    where F: Fn(&[f64]) -> f64 {
+    let mut source = PseudoRandom::new(mc_params.seed);
+    simulate(bs_params, mc_params, &mut source, payoff)
+}
+
+/// Same as [`mc_premium`] but with custom source of normal variates (`mc_params.seed` is not used).
+/// Fails if the source is built for other number of time steps than `mc_params.steps`.
+pub fn mc_premium_with_source<R, F>(bs_params: &BlackScholesParams, mc_params: &McParams, source: &mut R, payoff: F) -> Result<McResult, String>
+    where R: RandomSource, F: Fn(&[f64]) -> f64 {
+    let steps = mc_params.steps.max(1);
+    match source.dimension() {
+        Some(d) if d != steps => Err(format!("random source dimension {} doesn't match {} time steps", d, steps)),
+        _ => Ok(simulate(bs_params, mc_params, source, payoff))
+    }
+}
+
+fn simulate<R, F>(bs_params: &BlackScholesParams, mc_params: &McParams, source: &mut R, payoff: F) -> McResult
+    where R: RandomSource, F: Fn(&[f64]) -> f64 {
+    let steps = mc_params.steps.max(1);
+    let dt = bs_params.time_to_expiry / steps as f64;
+    let drift = (bs_params.rate - bs_params.div_yield - 0.5 * bs_params.vol * bs_params.vol) * dt;
+    let diffusion = bs_params.vol * dt.sqrt();
+    let discount = (-bs_params.rate * bs_params.time_to_expiry).exp();
+    let control_mean = call_premium(bs_params);
+
+    let samples = if mc_params.antithetic {(mc_params.paths / 2).max(1)} else {mc_params.paths.max(1)};
+    let mut normals = vec![0.0; steps];
+    let mut path = vec![0.0; steps + 1];
+
+    // discounted payoff x and control y for each sample
+    let sample = |normals: &[f64], path: &mut [f64], sign: f64| {
+        path[0] = bs_params.price;
+        for i in 0..steps {
+            path[i + 1] = path[i] * (drift + sign * diffusion * normals[i]).exp();
+        }
+        (discount * payoff(path), discount * (path[steps] - bs_params.strike).max(0.0))
+    };
+
+    let (mut sx, mut sy, mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for _ in 0..samples {
+        source.next_normals(&mut normals);
+        let (mut x, mut y) = sample(&normals, &mut path, 1.0);
+        if mc_params.antithetic {
+            let (xa, ya) = sample(&normals, &mut path, -1.0);
+            x = 0.5 * (x + xa);
+            y = 0.5 * (y + ya);
+        }
+        sx += x;
+        sy += y;
+        sxx += x * x;
+        syy += y * y;
+        sxy += x * y;
+    }
+
+    let n = samples as f64;
+    let (mx, my) = (sx / n, sy / n);
+    let var_x = (sxx / n - mx * mx).max(0.0);
+    if !mc_params.control_variate {
+        return McResult { premium: mx, std_error: (var_x / n).sqrt() };
+    }
+
+    let var_y = syy / n - my * my;
+    let cov = sxy / n - mx * my;
+    let beta = if var_y > 0.0 {cov / var_y} else {0.0};
+    let var = (var_x - 2.0 * beta * cov + beta * beta * var_y).max(0.0);
+    McResult { premium: mx - beta * (my - control_mean), std_error: (var / n).sqrt() }
+}
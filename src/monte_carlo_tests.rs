@@ -0,0 +1,59 @@
+#![cfg(test)]
+use crate::black_scholes;
+use crate::black_scholes::BlackScholesParams;
+use crate::monte_carlo::*;
+
+fn bs_params() -> BlackScholesParams {
+    BlackScholesParams { price: 100.0, strike: 105.0, rate: 0.04, div_yield: 0.01, vol: 0.3, time_to_expiry: 1.0 }
+}
+
+fn mc_params(antithetic: bool, control_variate: bool) -> McParams {
+    McParams { paths: 20000, steps: 1, seed: 11, antithetic, control_variate }
+}
+
+#[test]
+fn test_european_matches_closed_form() {
+    let p = bs_params();
+    let call = black_scholes::call_premium(&p);
+    let put = black_scholes::put_premium(&p);
+
+    for (antithetic, control_variate) in [(false, false), (true, false), (false, true), (true, true)].iter() {
+        let params = mc_params(*antithetic, *control_variate);
+        let mc_call = mc_premium(&p, &params, |path| (path[path.len() - 1] - p.strike).max(0.0));
+        assert!((mc_call.premium - call).abs() < 4.0 * mc_call.std_error.max(1e-9));
+
+        let mc_put = mc_premium(&p, &params, |path| (p.strike - path[path.len() - 1]).max(0.0));
+        assert!((mc_put.premium - put).abs() < 4.0 * mc_put.std_error);
+    }
+}
+
+#[test]
+fn test_control_variate_on_call_is_exact() {
+    let p = bs_params();
+    let res = mc_premium(&p, &mc_params(false, true), |path| (path[1] - p.strike).max(0.0));
+    assert!((res.premium - black_scholes::call_premium(&p)).abs() < 1e-9);
+    assert!(res.std_error < 1e-9);
+}
+
+#[test]
+fn test_variance_reduction() {
+    let p = bs_params();
+    let payoff = |path: &[f64]| (p.strike - path[path.len() - 1]).max(0.0);
+    let plain = mc_premium(&p, &mc_params(false, false), payoff);
+    let antithetic = mc_premium(&p, &mc_params(true, false), payoff);
+    let control = mc_premium(&p, &mc_params(false, true), payoff);
+    assert!(antithetic.std_error < plain.std_error);
+    assert!(control.std_error < plain.std_error);
+}
+
+#[test]
+fn test_seed_reproducibility() {
+    let p = bs_params();
+    let params = McParams { steps: 10, ..mc_params(true, false) };
+    let payoff = |path: &[f64]| path.iter().cloned().fold(0.0, f64::max) - path[path.len() - 1];
+    let r1 = mc_premium(&p, &params, payoff);
+    let r2 = mc_premium(&p, &params, payoff);
+    let r3 = mc_premium(&p, &McParams { seed: 12, ..params }, payoff);
+    assert_eq!(r1.premium, r2.premium);
+    assert!(r1.premium != r3.premium);
+}
@@ -0,0 +1,97 @@
+//! module with options on several underlying assets: exchange options (Margrabe), spread options
+//! (Kirk and Bjerksund-Stensland approximations) and basket options (lognormal moment matching).
+//! Each asset is described by its own [`BlackScholesParams`], whose strike is not used.
+//! Risk free rate and time to expiry are taken from the first asset.
+use rv::prelude::*;
+use crate::black_scholes::*;
+
+/// Option to exchange second asset for the first one, paying `max(S1 - S2, 0)` (Margrabe)
+pub fn exchange_premium(asset1: &BlackScholesParams, asset2: &BlackScholesParams, correlation: f64) -> f64 {
+    // second asset plays the role of strike, its dividend yield is the discount rate of the strike
+    call_premium(&BlackScholesParams {
+        price: asset1.price,
+        strike: asset2.price,
+        rate: asset2.div_yield,
+        div_yield: asset1.div_yield,
+        vol: spread_vol(asset1.vol, asset2.vol, correlation, 1.0),
+        time_to_expiry: asset1.time_to_expiry
+    })
+}
+
+/// Spread option premium, paying `max(S1 - S2 - K, 0)` for call and `max(K - S1 + S2, 0)` for put (Kirk approximation)
+pub fn kirk_spread_premium(is_call: bool, asset1: &BlackScholesParams, asset2: &BlackScholesParams, correlation: f64, strike: f64) -> f64 {
+    let (f1, f2) = (forward(asset1, asset1), forward(asset2, asset1));
+    let b = f2 / (f2 + strike);
+    black_premium(is_call, asset1, f1, f2 + strike, spread_vol(asset1.vol, asset2.vol, correlation, b))
+}
+
+/// Spread option premium with Bjerksund and Stensland (2011) approximation, which is more accurate than Kirk's
+/// for strikes far from zero
+pub fn bjerksund_stensland_spread_premium(is_call: bool, asset1: &BlackScholesParams, asset2: &BlackScholesParams, correlation: f64, strike: f64) -> f64 {
+    let sign = if is_call {1.0} else {-1.0};
+    let n: Gaussian = Gaussian::standard();
+    let t = asset1.time_to_expiry;
+    let (f1, f2) = (forward(asset1, asset1), forward(asset2, asset1));
+    let a = f2 + strike;
+    let b = f2 / a;
+    let (v1, v2) = (asset1.vol, asset2.vol);
+    let cov = correlation * v1 * v2;
+    let vt = spread_vol(v1, v2, correlation, b) * t.sqrt();
+    let lfa = (f1 / a).ln();
+
+    let d1 = (lfa + (v1 * v1 / 2.0 - b * cov + b * b * v2 * v2 / 2.0) * t) / vt;
+    let d2 = (lfa + (-v1 * v1 / 2.0 + cov + (b * b / 2.0 - b) * v2 * v2) * t) / vt;
+    let d3 = (lfa + (-v1 * v1 / 2.0 + b * b * v2 * v2 / 2.0) * t) / vt;
+
+    (-asset1.rate * t).exp() * sign * (f1 * n.cdf(&(sign * d1)) - f2 * n.cdf(&(sign * d2)) - strike * n.cdf(&(sign * d3)))
+}
+
+/// Basket option premium on weighted sum of assets, approximating basket with lognormal variable
+/// with the same first two moments
+/// * correlations - correlation matrix of assets
+pub fn basket_premium(is_call: bool, assets: &[BlackScholesParams], weights: &[f64], correlations: &[Vec<f64>], strike: f64) -> Result<f64, String> {
+    if assets.is_empty() || assets.len() != weights.len() || assets.len() != correlations.len() || correlations.iter().any(|row| row.len() != assets.len()) {
+        return Err("assets, weights and correlation matrix must have the same dimension".to_string());
+    }
+
+    let t = assets[0].time_to_expiry;
+    let forwards: Vec<f64> = assets.iter().zip(weights.iter()).map(|(a, w)| w * forward(a, &assets[0])).collect();
+    let first: f64 = forwards.iter().sum();
+    if first <= 0.0 {
+        return Err("basket forward must be positive".to_string());
+    }
+
+    let mut second = 0.0;
+    for i in 0..assets.len() {
+        for j in 0..assets.len() {
+            second += forwards[i] * forwards[j] * (correlations[i][j] * assets[i].vol * assets[j].vol * t).exp();
+        }
+    }
+    let vol = ((second / (first * first)).ln() / t).sqrt();
+    Ok(black_premium(is_call, &assets[0], first, strike, vol))
+}
+
+/// Forward price of the asset, with rate and time to expiry of `market`
+#[inline]
+fn forward(asset: &BlackScholesParams, market: &BlackScholesParams) -> f64 {
+    asset.price * ((market.rate - asset.div_yield) * market.time_to_expiry).exp()
+}
+
+/// Volatility of `S1 - b * S2` in lognormal approximation
+#[inline]
+fn spread_vol(vol1: f64, vol2: f64, correlation: f64, b: f64) -> f64 {
+    (vol1 * vol1 - 2.0 * b * correlation * vol1 * vol2 + b * b * vol2 * vol2).sqrt()
+}
+
+/// Black formula for option on forward, with rate and time to expiry of `market`
+#[inline]
+fn black_premium(is_call: bool, market: &BlackScholesParams, forward: f64, strike: f64, vol: f64) -> f64 {
+    generic_black_scholes(is_call, &BlackScholesParams {
+        price: forward,
+        strike,
+        rate: market.rate,
+        div_yield: market.rate,
+        vol,
+        time_to_expiry: market.time_to_expiry
+    })
+}
@@ -0,0 +1,89 @@
+#![cfg(test)]
+use rand::prelude::*;
+use rv::prelude::*;
+use crate::black_scholes;
+use crate::black_scholes::BlackScholesParams;
+use crate::multi_asset::*;
+
+const EPS: f64 = 0.0001;
+
+fn asset(price: f64, div_yield: f64, vol: f64) -> BlackScholesParams {
+    BlackScholesParams { price, strike: 0.0, rate: 0.05, div_yield, vol, time_to_expiry: 0.5 }
+}
+
+/// Monte Carlo estimate of `payoff` of terminal prices of two correlated assets, returns mean and standard error
+fn mc_two_assets<F>(a1: &BlackScholesParams, a2: &BlackScholesParams, correlation: f64, payoff: F) -> (f64, f64)
+    where F: Fn(f64, f64) -> f64 {
+    let n: Gaussian = Gaussian::standard();
+    let mut rng = StdRng::seed_from_u64(1);
+    let t = a1.time_to_expiry;
+    let terminal = |a: &BlackScholesParams, z: f64| a.price * ((a1.rate - a.div_yield - a.vol * a.vol / 2.0) * t + a.vol * t.sqrt() * z).exp();
+    let paths = 200000;
+    let (mut sum, mut sum_sq) = (0.0, 0.0);
+    for _ in 0..paths {
+        let z1: f64 = n.draw(&mut rng);
+        let e: f64 = n.draw(&mut rng);
+        let z2 = correlation * z1 + (1.0 - correlation * correlation).sqrt() * e;
+        let x = payoff(terminal(a1, z1), terminal(a2, z2));
+        sum += x;
+        sum_sq += x * x;
+    }
+    let mean = sum / paths as f64;
+    let discount = (-a1.rate * t).exp();
+    (discount * mean, discount * ((sum_sq / paths as f64 - mean * mean) / paths as f64).sqrt())
+}
+
+#[test]
+fn test_exchange_option() {
+    // riskless second asset growing at risk free rate is a fixed strike
+    let a1 = asset(22.0, 0.06, 0.2);
+    let a2 = BlackScholesParams { div_yield: a1.rate, vol: 0.0, ..asset(20.0, 0.0, 0.0) };
+    let vanilla = black_scholes::call_premium(&BlackScholesParams { strike: 20.0, ..a1 });
+    assert!((exchange_premium(&a1, &a2, 0.3) - vanilla).abs() < EPS);
+
+    let a2 = asset(20.0, 0.04, 0.25);
+    let (mc, err) = mc_two_assets(&a1, &a2, -0.5, |s1, s2| (s1 - s2).max(0.0));
+    assert!((exchange_premium(&a1, &a2, -0.5) - mc).abs() < 3.0 * err);
+}
+
+#[test]
+fn test_spread_with_zero_strike_is_exchange() {
+    let (a1, a2) = (asset(105.0, 0.02, 0.3), asset(100.0, 0.01, 0.2));
+    let exchange = exchange_premium(&a1, &a2, 0.6);
+    assert!((kirk_spread_premium(true, &a1, &a2, 0.6, 0.0) - exchange).abs() < EPS);
+    assert!((bjerksund_stensland_spread_premium(true, &a1, &a2, 0.6, 0.0) - exchange).abs() < EPS);
+}
+
+#[test]
+fn test_spread_against_monte_carlo() {
+    let (a1, a2) = (asset(110.0, 0.02, 0.35), asset(100.0, 0.01, 0.25));
+    let correlation = 0.7;
+    for strike in [-5.0, 5.0, 20.0].iter() {
+        let (mc_call, err) = mc_two_assets(&a1, &a2, correlation, |s1, s2| (s1 - s2 - strike).max(0.0));
+        let kirk = kirk_spread_premium(true, &a1, &a2, correlation, *strike);
+        let bs = bjerksund_stensland_spread_premium(true, &a1, &a2, correlation, *strike);
+        assert!((kirk - mc_call).abs() < 3.0 * err + 0.05, "strike {}: {} vs {}", strike, kirk, mc_call);
+        assert!((bs - mc_call).abs() < 3.0 * err + 0.02, "strike {}: {} vs {}", strike, bs, mc_call);
+
+        let (mc_put, err) = mc_two_assets(&a1, &a2, correlation, |s1, s2| (strike + s2 - s1).max(0.0));
+        let bs_put = bjerksund_stensland_spread_premium(false, &a1, &a2, correlation, *strike);
+        assert!((bs_put - mc_put).abs() < 3.0 * err + 0.02, "strike {}: {} vs {}", strike, bs_put, mc_put);
+        assert!((kirk_spread_premium(false, &a1, &a2, correlation, *strike) - mc_put).abs() < 3.0 * err + 0.05);
+    }
+}
+
+#[test]
+fn test_basket() {
+    let a = BlackScholesParams { strike: 100.0, ..asset(100.0, 0.01, 0.25) };
+    // single asset basket is vanilla option
+    let single = basket_premium(true, &[asset(100.0, 0.01, 0.25)], &[1.0], &[vec![1.0]], 100.0).unwrap();
+    assert!((single - black_scholes::call_premium(&a)).abs() < EPS);
+
+    let (a1, a2) = (asset(100.0, 0.02, 0.3), asset(50.0, 0.0, 0.2));
+    let correlations = vec![vec![1.0, 0.4], vec![0.4, 1.0]];
+    let basket = basket_premium(false, &[a1, a2], &[0.5, 1.0], &correlations, 100.0).unwrap();
+    let (mc, err) = mc_two_assets(&asset(100.0, 0.02, 0.3), &asset(50.0, 0.0, 0.2), 0.4, |s1, s2| (100.0 - 0.5 * s1 - s2).max(0.0));
+    assert!((basket - mc).abs() < 3.0 * err + 0.05, "{} vs {}", basket, mc);
+
+    assert!(basket_premium(true, &[asset(100.0, 0.0, 0.2)], &[1.0, 2.0], &[vec![1.0]], 100.0).is_err());
+}
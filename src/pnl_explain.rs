@@ -0,0 +1,93 @@
+//! module with P&L attribution (greek explain) between two market states: change of option value from full
+//! revaluation is split into delta, gamma, vega, theta, rho, vanna and volga terms calculated with greeks
+//! of the first state, the rest is unexplained.
+use crate::black_scholes::*;
+use crate::portfolio::*;
+
+/// P&L attribution terms, `actual` is the sum of all terms
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PnlExplain {
+    pub actual: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+    pub rho: f64,
+    pub vanna: f64,
+    pub volga: f64,
+    pub unexplained: f64
+}
+
+impl PnlExplain {
+
+    fn add_scaled(&mut self, other: &PnlExplain, scale: f64) {
+        self.actual += scale * other.actual;
+        self.delta += scale * other.delta;
+        self.gamma += scale * other.gamma;
+        self.vega += scale * other.vega;
+        self.theta += scale * other.theta;
+        self.rho += scale * other.rho;
+        self.vanna += scale * other.vanna;
+        self.volga += scale * other.volga;
+        self.unexplained += scale * other.unexplained;
+    }
+}
+
+/// P&L attribution of one option from state `before` to state `after`, elapsed time is the decrease of
+/// time to expiry. Change of dividend yield is left unexplained.
+pub fn option_pnl_explain(is_call: bool, before: &BlackScholesParams, after: &BlackScholesParams) -> PnlExplain {
+    let greeks = option_greeks(is_call, before);
+    let actual = generic_black_scholes(is_call, after) - greeks.premium;
+    let ds = after.price - before.price;
+    // volatility and rate changes in percents as vega and rho are per 1%
+    let dvol = 100.0 * (after.vol - before.vol);
+    let drate = 100.0 * (after.rate - before.rate);
+    let dt = before.time_to_expiry - after.time_to_expiry;
+
+    let mut explain = PnlExplain {
+        actual,
+        delta: greeks.delta * ds,
+        gamma: 0.5 * greeks.gamma * ds * ds,
+        vega: greeks.vega * dvol,
+        theta: greeks.theta * dt,
+        rho: greeks.rho * drate,
+        vanna: vanna(before) * ds * dvol,
+        volga: 0.5 * volga(before) * dvol * dvol,
+        unexplained: 0.0
+    };
+    explain.unexplained = actual - explain.delta - explain.gamma - explain.vega - explain.theta - explain.rho - explain.vanna - explain.volga;
+    explain
+}
+
+/// P&L attribution of portfolio between two snapshots. Both portfolios must have the same positions in the same order,
+/// which may differ only by option volatilities and times to expiry, and market data for all underlyings.
+pub fn portfolio_pnl_explain(before: &Portfolio, after: &Portfolio) -> Result<PnlExplain, String> {
+    if before.positions().len() != after.positions().len() {
+        return Err("portfolios have different number of positions".to_string());
+    }
+    let market = |portfolio: &Portfolio, underlying: &str| portfolio.market(underlying).copied()
+        .ok_or_else(|| format!("no market data for {}", underlying));
+
+    let mut total = PnlExplain::default();
+    for (i, (p0, p1)) in before.positions().iter().zip(after.positions().iter()).enumerate() {
+        let same_contract = match (p0.instrument, p1.instrument) {
+            (Instrument::Underlying, Instrument::Underlying) => true,
+            (Instrument::Option { is_call: c0, strike: k0, .. }, Instrument::Option { is_call: c1, strike: k1, .. }) => c0 == c1 && k0 == k1,
+            _ => false
+        };
+        if !same_contract || p0.underlying != p1.underlying || p0.quantity != p1.quantity || p0.multiplier != p1.multiplier {
+            return Err(format!("position {} is different in portfolios", i + 1));
+        }
+        let (m0, m1) = (market(before, &p0.underlying)?, market(after, &p1.underlying)?);
+        let units = p0.quantity * p0.multiplier;
+        let explain = match (p0.instrument, p0.bs_params(&m0), p1.bs_params(&m1)) {
+            (Instrument::Option { is_call, .. }, Some(b0), Some(b1)) => option_pnl_explain(is_call, &b0, &b1),
+            _ => {
+                let ds = m1.price - m0.price;
+                PnlExplain { actual: ds, delta: ds, ..PnlExplain::default() }
+            }
+        };
+        total.add_scaled(&explain, units);
+    }
+    Ok(total)
+}
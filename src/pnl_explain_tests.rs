@@ -0,0 +1,80 @@
+#![cfg(test)]
+use crate::black_scholes::*;
+use crate::portfolio::*;
+use crate::pnl_explain::*;
+
+const EPS: f64 = 0.0001;
+
+fn before() -> BlackScholesParams {
+    BlackScholesParams { price: 100.0, strike: 105.0, rate: 0.04, div_yield: 0.01, vol: 0.22, time_to_expiry: 0.5 }
+}
+
+#[test]
+fn test_vanna_volga() {
+    let p = before();
+    let h = 0.0001;
+    let bump = |vol: f64| BlackScholesParams { vol, ..p };
+    let vanna_fd = 0.01 * (call_delta(&bump(p.vol + h)) - call_delta(&bump(p.vol - h))) / (2.0 * h);
+    let volga_fd = 0.01 * (vega(&bump(p.vol + h)) - vega(&bump(p.vol - h))) / (2.0 * h);
+    assert!((vanna(&p) - vanna_fd).abs() < 1e-8);
+    assert!((volga(&p) - volga_fd).abs() < 1e-8);
+    assert!((vanna(&p) - 0.01 * (put_delta(&bump(p.vol + h)) - put_delta(&bump(p.vol - h))) / (2.0 * h)).abs() < 1e-8);
+}
+
+#[test]
+fn test_single_factor_moves() {
+    let p = before();
+    // each factor moves alone and is explained by its own terms
+    let spot = option_pnl_explain(true, &p, &BlackScholesParams { price: 101.0, ..p });
+    assert!((spot.delta + spot.gamma - spot.actual).abs() < 0.001);
+    assert_eq!((spot.vega, spot.theta, spot.vanna, spot.volga), (0.0, 0.0, 0.0, 0.0));
+
+    let vol = option_pnl_explain(false, &p, &BlackScholesParams { vol: 0.24, ..p });
+    assert!((vol.vega + vol.volga - vol.actual).abs() < 0.001);
+    assert!(vol.volga.abs() > 0.0 && vol.delta == 0.0);
+
+    let time = option_pnl_explain(true, &p, &BlackScholesParams { time_to_expiry: p.time_to_expiry - 1.0 / 365.0, ..p });
+    assert!(time.theta < 0.0);
+    assert!(time.unexplained.abs() < 0.01 * time.actual.abs());
+
+    let rate = option_pnl_explain(true, &p, &BlackScholesParams { rate: 0.045, ..p });
+    assert!((rate.rho - rate.actual).abs() < 0.001);
+}
+
+#[test]
+fn test_combined_move() {
+    let p = before();
+    let after = BlackScholesParams { price: 97.0, vol: 0.25, rate: 0.041, time_to_expiry: p.time_to_expiry - 1.0 / 365.0, ..p };
+    let explain = option_pnl_explain(true, &p, &after);
+    let sum = explain.delta + explain.gamma + explain.vega + explain.theta + explain.rho + explain.vanna + explain.volga + explain.unexplained;
+    assert!((sum - explain.actual).abs() < 1e-12);
+    assert!((explain.actual - (call_premium(&after) - call_premium(&p))).abs() < 1e-12);
+    // second order terms leave small residual
+    assert!(explain.unexplained.abs() < 0.05 * explain.actual.abs());
+    assert!(explain.vanna != 0.0);
+}
+
+#[test]
+fn test_portfolio_explain() {
+    let mut t0 = Portfolio::new();
+    t0.set_market("XYZ", MarketData { price: 100.0, rate: 0.04, div_yield: 0.01 });
+    t0.add_position(Position { underlying: "XYZ".to_string(), instrument: Instrument::Option { is_call: true, strike: 105.0, time_to_expiry: 0.5, vol: 0.22 }, quantity: 10.0, multiplier: 100.0 });
+    t0.add_position(Position { underlying: "XYZ".to_string(), instrument: Instrument::Underlying, quantity: -400.0, multiplier: 1.0 });
+
+    let mut t1 = Portfolio::new();
+    t1.set_market("XYZ", MarketData { price: 102.0, rate: 0.04, div_yield: 0.01 });
+    t1.add_position(Position { instrument: Instrument::Option { is_call: true, strike: 105.0, time_to_expiry: 0.5 - 1.0 / 365.0, vol: 0.21 }, ..t0.positions()[0].clone() });
+    t1.add_position(t0.positions()[1].clone());
+
+    let explain = portfolio_pnl_explain(&t0, &t1).unwrap();
+    let actual = t1.total_risk().unwrap().premium - t0.total_risk().unwrap().premium;
+    assert!((explain.actual - actual).abs() < 1e-8);
+    let option = option_pnl_explain(true, &t0.positions()[0].bs_params(t0.market("XYZ").unwrap()).unwrap(),
+                                    &t1.positions()[0].bs_params(t1.market("XYZ").unwrap()).unwrap());
+    assert!((explain.delta - (1000.0 * option.delta - 400.0 * 2.0)).abs() < EPS);
+    assert!((explain.vega - 1000.0 * option.vega).abs() < EPS);
+
+    let mut other = t1.clone();
+    other.add_position(t0.positions()[1].clone());
+    assert!(portfolio_pnl_explain(&t0, &other).is_err());
+}
@@ -0,0 +1,201 @@
+//! module with portfolio of option and underlying positions over shared market data of underlyings.
+//! Risk is aggregated with quantities and contract multipliers into premium, dollar delta, dollar gamma,
+//! vega, theta and rho, in total or grouped by underlying and expiry.
+//!
+//! # Example
+//! ```
+//! use ivol::portfolio::*;
+//!
+//! let mut portfolio = Portfolio::new();
+//! portfolio.set_market("SPX", MarketData{price: 4800.0, rate: 0.05, div_yield: 0.015});
+//! let call = Instrument::Option{is_call: true, strike: 4800.0, time_to_expiry: 0.25, vol: 0.15};
+//! portfolio.add_position(Position{underlying: "SPX".to_string(), instrument: call, quantity: 10.0, multiplier: 100.0});
+//! portfolio.add_position(Position{underlying: "SPX".to_string(), instrument: Instrument::Underlying, quantity: -560.0, multiplier: 1.0});
+//!
+//! let risk = portfolio.total_risk().unwrap();
+//! // delta hedged with the underlying
+//! assert!(risk.dollar_delta.abs() < 0.01 * 4800.0 * 1000.0);
+//! ```
+use std::collections::BTreeMap;
+use crate::black_scholes::*;
+
+/// Market data of underlying asset shared by all its positions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarketData {
+    pub price: f64,
+    pub rate: f64,
+    pub div_yield: f64
+}
+
+/// Traded instrument
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Instrument {
+    /// underlying asset itself
+    Underlying,
+    /// european option on underlying asset
+    Option { is_call: bool, strike: f64, time_to_expiry: f64, vol: f64 }
+}
+
+/// Position in instrument on named underlying
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub underlying: String,
+    pub instrument: Instrument,
+    /// number of contracts, negative for short position
+    pub quantity: f64,
+    /// number of units of underlying per contract
+    pub multiplier: f64
+}
+
+/// Aggregated risk of positions
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RiskSummary {
+    /// market value
+    pub premium: f64,
+    /// value change per unit change of underlying price times underlying price
+    pub dollar_delta: f64,
+    /// delta change for 1% change of underlying price times underlying price
+    pub dollar_gamma: f64,
+    /// value change for 1% volatility change
+    pub vega: f64,
+    /// value change per year
+    pub theta: f64,
+    /// value change for 1% rate change
+    pub rho: f64
+}
+
+/// Risk of positions with the same underlying and expiry, `expiry` is `None` for underlying positions
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskGroup {
+    pub underlying: String,
+    pub expiry: Option<f64>,
+    pub risk: RiskSummary
+}
+
+impl RiskSummary {
+
+    fn add(&mut self, other: &RiskSummary) {
+        self.premium += other.premium;
+        self.dollar_delta += other.dollar_delta;
+        self.dollar_gamma += other.dollar_gamma;
+        self.vega += other.vega;
+        self.theta += other.theta;
+        self.rho += other.rho;
+    }
+}
+
+impl Position {
+
+    /// Black/Scholes parameters of option position, `None` for underlying
+    pub fn bs_params(&self, market: &MarketData) -> Option<BlackScholesParams> {
+        match self.instrument {
+            Instrument::Underlying => None,
+            Instrument::Option { strike, time_to_expiry, vol, .. } => Some(BlackScholesParams {
+                price: market.price,
+                strike,
+                rate: market.rate,
+                div_yield: market.div_yield,
+                vol,
+                time_to_expiry
+            })
+        }
+    }
+
+    /// Risk of position with quantity and multiplier
+    pub fn risk(&self, market: &MarketData) -> RiskSummary {
+        let units = self.quantity * self.multiplier;
+        let s = market.price;
+        let greeks = match (self.instrument, self.bs_params(market)) {
+            (Instrument::Option { is_call, .. }, Some(p)) => option_greeks(is_call, &p),
+            _ => Greeks { premium: s, delta: 1.0, ..Greeks::default() }
+        };
+        RiskSummary {
+            premium: units * greeks.premium,
+            dollar_delta: units * greeks.delta * s,
+            dollar_gamma: units * greeks.gamma * s * s / 100.0,
+            vega: units * greeks.vega,
+            theta: units * greeks.theta,
+            rho: units * greeks.rho
+        }
+    }
+
+    /// Expiry of the instrument, `None` for underlying
+    pub fn expiry(&self) -> Option<f64> {
+        match self.instrument {
+            Instrument::Underlying => None,
+            Instrument::Option { time_to_expiry, .. } => Some(time_to_expiry)
+        }
+    }
+}
+
+/// Positions with market data of their underlyings
+#[derive(Debug, Clone, Default)]
+pub struct Portfolio {
+    markets: BTreeMap<String, MarketData>,
+    positions: Vec<Position>
+}
+
+impl Portfolio {
+
+    pub fn new() -> Portfolio {
+        Portfolio::default()
+    }
+
+    /// Sets or replaces market data of underlying
+    pub fn set_market(&mut self, underlying: &str, market: MarketData) {
+        self.markets.insert(underlying.to_string(), market);
+    }
+
+    pub fn market(&self, underlying: &str) -> Option<&MarketData> {
+        self.markets.get(underlying)
+    }
+
+    pub fn add_position(&mut self, position: Position) {
+        self.positions.push(position);
+    }
+
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Risk of each position in the order of positions, fails if market data of some underlying is missing
+    pub fn position_risks(&self) -> Result<Vec<RiskSummary>, String> {
+        self.positions.iter().map(|p| {
+            let market = self.markets.get(&p.underlying).ok_or_else(|| format!("no market data for {}", p.underlying))?;
+            Ok(p.risk(market))
+        }).collect()
+    }
+
+    /// Netted risk of all positions
+    pub fn total_risk(&self) -> Result<RiskSummary, String> {
+        let mut total = RiskSummary::default();
+        for risk in self.position_risks()?.iter() {
+            total.add(risk);
+        }
+        Ok(total)
+    }
+
+    /// Netted risk for each underlying, ordered by underlying name
+    pub fn risk_by_underlying(&self) -> Result<BTreeMap<String, RiskSummary>, String> {
+        let mut groups: BTreeMap<String, RiskSummary> = BTreeMap::new();
+        for (position, risk) in self.positions.iter().zip(self.position_risks()?.iter()) {
+            groups.entry(position.underlying.clone()).or_default().add(risk);
+        }
+        Ok(groups)
+    }
+
+    /// Netted risk for each underlying and expiry, ordered by underlying name and expiry
+    /// with underlying positions first
+    pub fn risk_by_expiry(&self) -> Result<Vec<RiskGroup>, String> {
+        let mut groups: Vec<RiskGroup> = Vec::new();
+        for (position, risk) in self.positions.iter().zip(self.position_risks()?.iter()) {
+            let expiry = position.expiry();
+            match groups.iter_mut().find(|g| g.underlying == position.underlying && g.expiry == expiry) {
+                Some(group) => group.risk.add(risk),
+                None => groups.push(RiskGroup { underlying: position.underlying.clone(), expiry, risk: *risk })
+            }
+        }
+        groups.sort_by(|a, b| a.underlying.cmp(&b.underlying).then(a.expiry.partial_cmp(&b.expiry).unwrap_or(std::cmp::Ordering::Equal)));
+        Ok(groups)
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg(test)]
+use crate::black_scholes::*;
+use crate::portfolio::*;
+
+const EPS: f64 = 0.0001;
+
+fn option(is_call: bool, strike: f64, time_to_expiry: f64) -> Instrument {
+    Instrument::Option { is_call, strike, time_to_expiry, vol: 0.2 }
+}
+
+fn position(underlying: &str, instrument: Instrument, quantity: f64, multiplier: f64) -> Position {
+    Position { underlying: underlying.to_string(), instrument, quantity, multiplier }
+}
+
+#[test]
+fn test_option_position() {
+    let market = MarketData { price: 100.0, rate: 0.05, div_yield: 0.01 };
+    let p = position("XYZ", option(false, 95.0, 0.5), -3.0, 100.0);
+    let bs_params = p.bs_params(&market).unwrap();
+    let greeks = option_greeks(false, &bs_params);
+    let risk = p.risk(&market);
+    assert!((risk.premium + 300.0 * greeks.premium).abs() < 1e-9);
+    assert!((risk.dollar_delta + 300.0 * greeks.delta * 100.0).abs() < 1e-9);
+    assert!((risk.dollar_gamma + 300.0 * greeks.gamma * 100.0).abs() < 1e-9);
+    assert!((risk.vega + 300.0 * greeks.vega).abs() < 1e-9);
+    assert!((risk.theta + 300.0 * greeks.theta).abs() < 1e-9);
+    assert!((risk.rho + 300.0 * greeks.rho).abs() < 1e-9);
+
+    // dollar gamma is delta change for 1% spot move valued at spot
+    let delta = |price: f64| option_greeks(false, &BlackScholesParams { price, ..bs_params }).delta;
+    let shift = -300.0 * (delta(101.0) - delta(99.0)) / 2.0 * 100.0;
+    assert!((shift / risk.dollar_gamma - 1.0).abs() < 0.001);
+}
+
+#[test]
+fn test_netted_risk() {
+    // long call, short put and short discounted underlying is riskless
+    let mut portfolio = Portfolio::new();
+    let market = MarketData { price: 100.0, rate: 0.05, div_yield: 0.02 };
+    portfolio.set_market("XYZ", market);
+    portfolio.add_position(position("XYZ", option(true, 100.0, 1.0), 1.0, 100.0));
+    portfolio.add_position(position("XYZ", option(false, 100.0, 1.0), -1.0, 100.0));
+    portfolio.add_position(position("XYZ", Instrument::Underlying, -100.0 * (-market.div_yield).exp(), 1.0));
+    let risk = portfolio.total_risk().unwrap();
+    assert!(risk.dollar_delta.abs() < 1e-8);
+    assert!(risk.dollar_gamma.abs() < 1e-8);
+    assert!(risk.vega.abs() < 1e-8);
+    // remaining value is short discounted strike
+    assert!((risk.premium + 100.0 * 100.0 * (-market.rate).exp()).abs() < EPS);
+
+    portfolio.add_position(position("ABC", Instrument::Underlying, 1.0, 1.0));
+    assert!(portfolio.total_risk().is_err());
+}
+
+#[test]
+fn test_grouping() {
+    let mut portfolio = Portfolio::new();
+    portfolio.set_market("B", MarketData { price: 50.0, rate: 0.03, div_yield: 0.0 });
+    portfolio.set_market("A", MarketData { price: 200.0, rate: 0.03, div_yield: 0.01 });
+    portfolio.add_position(position("B", option(true, 50.0, 0.5), 2.0, 100.0));
+    portfolio.add_position(position("A", option(true, 210.0, 1.0), 1.0, 10.0));
+    portfolio.add_position(position("A", option(false, 190.0, 0.25), 5.0, 10.0));
+    portfolio.add_position(position("A", Instrument::Underlying, -20.0, 1.0));
+    portfolio.add_position(position("A", option(false, 180.0, 1.0), -1.0, 10.0));
+
+    let risks = portfolio.position_risks().unwrap();
+    let by_expiry = portfolio.risk_by_expiry().unwrap();
+    let keys: Vec<(&str, Option<f64>)> = by_expiry.iter().map(|g| (g.underlying.as_str(), g.expiry)).collect();
+    assert_eq!(keys, vec![("A", None), ("A", Some(0.25)), ("A", Some(1.0)), ("B", Some(0.5))]);
+    assert!((by_expiry[2].risk.vega - risks[1].vega - risks[4].vega).abs() < 1e-10);
+    assert_eq!(by_expiry[0].risk.dollar_delta, -20.0 * 200.0);
+
+    let by_underlying = portfolio.risk_by_underlying().unwrap();
+    assert_eq!(by_underlying.keys().collect::<Vec<&String>>(), vec!["A", "B"]);
+    let total = portfolio.total_risk().unwrap();
+    assert!((by_underlying["A"].premium + by_underlying["B"].premium - total.premium).abs() < 1e-9);
+    assert!((by_underlying["B"].theta - risks[0].theta).abs() < 1e-10);
+}
@@ -0,0 +1,50 @@
+//! module with options on a foreign asset settled in domestic currency: quantos (payoff converted at a fixed
+//! exchange rate) and composites (foreign asset price converted at the exchange rate at expiry).
+//! The underlying is described by [`BlackScholesParams`] in its own currency, so its `rate` is the foreign
+//! risk free rate. Premiums are in domestic currency.
+use crate::black_scholes::*;
+
+/// Exchange rate and domestic market parameters
+#[derive(Debug, Clone, Copy)]
+pub struct FxParams {
+    /// spot exchange rate, units of domestic currency per unit of foreign currency
+    pub fx_rate: f64,
+    /// volatility of the exchange rate
+    pub fx_vol: f64,
+    /// correlation between underlying price and exchange rate
+    pub correlation: f64,
+    /// domestic risk free rate
+    pub domestic_rate: f64
+}
+
+/// Quanto option premium, paying `fixed_rate * max(S - K, 0)` for call and `fixed_rate * max(K - S, 0)` for put
+/// in domestic currency. The correlation drift adjustment lowers the underlying forward when the asset and
+/// the exchange rate move together. `fx_rate` of `fx_params` is not used.
+pub fn quanto_premium(is_call: bool, bs_params: &BlackScholesParams, fx_params: &FxParams, fixed_rate: f64) -> f64 {
+    // under domestic measure the asset drifts at foreign rate less dividends less quanto adjustment,
+    // which is expressed as an extra dividend yield with domestic discounting
+    let adjustment = fx_params.correlation * bs_params.vol * fx_params.fx_vol;
+    fixed_rate * generic_black_scholes(is_call, &BlackScholesParams {
+        rate: fx_params.domestic_rate,
+        div_yield: bs_params.div_yield + fx_params.domestic_rate - bs_params.rate + adjustment,
+        ..*bs_params
+    })
+}
+
+/// Composite option premium, paying `max(S * X - K, 0)` for call and `max(K - S * X, 0)` for put, where `X` is
+/// the exchange rate at expiry. Strike of `bs_params` is in domestic currency.
+pub fn composite_premium(is_call: bool, bs_params: &BlackScholesParams, fx_params: &FxParams) -> f64 {
+    // asset price in domestic currency is lognormal with combined volatility and the same dividend yield
+    generic_black_scholes(is_call, &BlackScholesParams {
+        price: bs_params.price * fx_params.fx_rate,
+        rate: fx_params.domestic_rate,
+        vol: composite_vol(bs_params.vol, fx_params),
+        ..*bs_params
+    })
+}
+
+/// Volatility of the asset price converted to domestic currency
+#[inline]
+pub fn composite_vol(vol: f64, fx_params: &FxParams) -> f64 {
+    (vol * vol + fx_params.fx_vol * fx_params.fx_vol + 2.0 * fx_params.correlation * vol * fx_params.fx_vol).sqrt()
+}
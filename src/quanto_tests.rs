@@ -0,0 +1,81 @@
+#![cfg(test)]
+use rand::prelude::*;
+use rv::prelude::*;
+use crate::black_scholes::*;
+use crate::quanto::*;
+
+const EPS: f64 = 0.0001;
+
+fn nikkei() -> BlackScholesParams {
+    BlackScholesParams { price: 38000.0, strike: 39000.0, rate: 0.005, div_yield: 0.018, vol: 0.22, time_to_expiry: 0.75 }
+}
+
+fn usd_jpy() -> FxParams {
+    FxParams { fx_rate: 1.0 / 150.0, fx_vol: 0.11, correlation: -0.35, domestic_rate: 0.045 }
+}
+
+/// Monte Carlo estimate of `payoff` of terminal asset price and exchange rate under domestic measure,
+/// returns discounted mean and standard error
+fn mc_fx<F>(asset: &BlackScholesParams, fx: &FxParams, payoff: F) -> (f64, f64)
+    where F: Fn(f64, f64) -> f64 {
+    let n: Gaussian = Gaussian::standard();
+    let mut rng = StdRng::seed_from_u64(7);
+    let t = asset.time_to_expiry;
+    let (vs, vx, rho) = (asset.vol, fx.fx_vol, fx.correlation);
+    let paths = 200000;
+    let (mut sum, mut sum_sq) = (0.0, 0.0);
+    for _ in 0..paths {
+        let z1: f64 = n.draw(&mut rng);
+        let e: f64 = n.draw(&mut rng);
+        let z2 = rho * z1 + (1.0 - rho * rho).sqrt() * e;
+        let s = asset.price * ((asset.rate - asset.div_yield - rho * vs * vx - vs * vs / 2.0) * t + vs * t.sqrt() * z1).exp();
+        let x = fx.fx_rate * ((fx.domestic_rate - asset.rate - vx * vx / 2.0) * t + vx * t.sqrt() * z2).exp();
+        let v = payoff(s, x);
+        sum += v;
+        sum_sq += v * v;
+    }
+    let mean = sum / paths as f64;
+    let discount = (-fx.domestic_rate * t).exp();
+    (discount * mean, discount * ((sum_sq / paths as f64 - mean * mean) / paths as f64).sqrt())
+}
+
+#[test]
+fn test_quanto_without_fx_risk() {
+    // with equal rates and uncorrelated exchange rate quanto is the vanilla option times fixed rate
+    let p = nikkei();
+    let fx = FxParams { correlation: 0.0, domestic_rate: p.rate, ..usd_jpy() };
+    for is_call in [true, false] {
+        let vanilla = generic_black_scholes(is_call, &p);
+        assert!((quanto_premium(is_call, &p, &fx, 0.01) - 0.01 * vanilla).abs() < EPS);
+    }
+}
+
+#[test]
+fn test_quanto_monte_carlo() {
+    let p = nikkei();
+    let fx = usd_jpy();
+    let fixed_rate = 1.0 / 145.0;
+    for is_call in [true, false] {
+        let sign = if is_call {1.0} else {-1.0};
+        let (mc, se) = mc_fx(&p, &fx, |s, _| fixed_rate * (sign * (s - p.strike)).max(0.0));
+        assert!((quanto_premium(is_call, &p, &fx, fixed_rate) - mc).abs() < 4.0 * se);
+    }
+}
+
+#[test]
+fn test_composite() {
+    let p = BlackScholesParams { strike: 260.0, ..nikkei() };
+    let fx = usd_jpy();
+    for is_call in [true, false] {
+        let sign = if is_call {1.0} else {-1.0};
+        let (mc, se) = mc_fx(&p, &fx, |s, x| (sign * (s * x - p.strike)).max(0.0));
+        assert!((composite_premium(is_call, &p, &fx) - mc).abs() < 4.0 * se);
+    }
+
+    // put-call parity in domestic currency
+    let call = composite_premium(true, &p, &fx);
+    let put = composite_premium(false, &p, &fx);
+    let t = p.time_to_expiry;
+    let forward = p.price * fx.fx_rate * (-p.div_yield * t).exp() - p.strike * (-fx.domestic_rate * t).exp();
+    assert!((call - put - forward).abs() < EPS);
+}
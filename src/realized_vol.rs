@@ -0,0 +1,155 @@
+//! module with historical (realized) volatility estimators from open, high, low and close prices:
+//! close-to-close, Parkinson, Garman-Klass, Rogers-Satchell and Yang-Zhang. Estimates are annualized and
+//! can be used as [`BlackScholesParams`](crate::black_scholes::BlackScholesParams) volatility or compared with implied volatility.
+//!
+//! # Example
+//! ```
+//! use ivol::black_scholes::*;
+//! use ivol::realized_vol::*;
+//!
+//! let lines: Vec<String> = ["date,open,high,low,close", "2024-01-02,100.0,101.2,99.1,100.8", "2024-01-03,100.6,102.0,100.1,101.5",
+//!     "2024-01-04,101.9,102.3,100.2,100.4", "2024-01-05,100.1,100.9,98.8,99.2"].iter().map(|l| l.to_string()).collect();
+//! let bars = bars_from_csv(&lines).unwrap();
+//! let realized = realized_vol(VolEstimator::YangZhang, &bars, TRADING_DAYS).unwrap();
+//!
+//! let bs_params = BlackScholesParams{price: 99.2, strike: 100.0, div_yield: 0.0, rate: 0.04, vol: 0.2, time_to_expiry: 0.1};
+//! let implied = call_impl_vol(&2.0, &bs_params).unwrap();
+//! let premium = call_premium(&BlackScholesParams{vol: realized, ..bs_params});
+//! assert!((premium > 2.0) == (realized > implied));
+//! ```
+
+/// Trading days in year, annualization factor of daily bars
+pub const TRADING_DAYS: f64 = 252.0;
+
+/// Open, high, low and close prices of one period
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bar {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64
+}
+
+/// Volatility estimator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VolEstimator {
+    /// standard deviation of close to close log returns
+    CloseToClose,
+    /// high-low range estimator, assumes no drift and no opening jumps
+    Parkinson,
+    /// open, high, low and close estimator, assumes no drift and no opening jumps
+    GarmanKlass,
+    /// open, high, low and close estimator, independent of drift, assumes no opening jumps
+    RogersSatchell,
+    /// combination of overnight, open to close and Rogers-Satchell variances, independent of drift and opening jumps
+    YangZhang
+}
+
+impl VolEstimator {
+
+    /// Minimal number of bars for the estimator
+    pub fn min_bars(&self) -> usize {
+        match self {
+            VolEstimator::CloseToClose | VolEstimator::YangZhang => 3,
+            _ => 1
+        }
+    }
+}
+
+/// Parses bars from CSV lines `date,open,high,low,close` in chronological order, date is not used.
+/// Header and empty lines are skipped.
+pub fn bars_from_csv(lines: &[String]) -> Result<Vec<Bar>, String> {
+    let mut bars = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim().trim_matches('"')).collect();
+        if line.trim().is_empty() || (i == 0 && fields.len() > 1 && fields[1].parse::<f64>().is_err()) {
+            continue;
+        }
+        if fields.len() != 5 {
+            return Err(format!("line {}: expected 5 fields, found {}", i + 1, fields.len()));
+        }
+        let number = |j: usize, name: &str| fields[j].parse::<f64>()
+            .map_err(|_| format!("line {}: {} is not a number: '{}'", i + 1, name, fields[j]));
+        let bar = Bar { open: number(1, "open")?, high: number(2, "high")?, low: number(3, "low")?, close: number(4, "close")? };
+        validate(&bar).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        bars.push(bar);
+    }
+    Ok(bars)
+}
+
+/// Annualized volatility estimate over all bars
+/// * periods_per_year - number of bars in year, e.g. [`TRADING_DAYS`] for daily bars
+pub fn realized_vol(estimator: VolEstimator, bars: &[Bar], periods_per_year: f64) -> Result<f64, String> {
+    validate_periods(periods_per_year)?;
+    if bars.len() < estimator.min_bars() {
+        return Err(format!("at least {} bars are needed for {:?} estimator", estimator.min_bars(), estimator));
+    }
+    for bar in bars.iter() {
+        validate(bar)?;
+    }
+    let n = bars.len() as f64;
+    let variance = match estimator {
+        VolEstimator::CloseToClose => {
+            let returns: Vec<f64> = bars.windows(2).map(|w| (w[1].close / w[0].close).ln()).collect();
+            sample_variance(&returns)
+        },
+        VolEstimator::Parkinson => bars.iter().map(|b| (b.high / b.low).ln().powi(2)).sum::<f64>() / (4.0 * 2f64.ln() * n),
+        VolEstimator::GarmanKlass => bars.iter().map(|b| {
+            0.5 * (b.high / b.low).ln().powi(2) - (2.0 * 2f64.ln() - 1.0) * (b.close / b.open).ln().powi(2)
+        }).sum::<f64>() / n,
+        VolEstimator::RogersSatchell => bars.iter().map(rogers_satchell).sum::<f64>() / n,
+        VolEstimator::YangZhang => {
+            // the first bar gives previous close only
+            let overnight: Vec<f64> = bars.windows(2).map(|w| (w[1].open / w[0].close).ln()).collect();
+            let open_close: Vec<f64> = bars[1..].iter().map(|b| (b.close / b.open).ln()).collect();
+            let m = (bars.len() - 1) as f64;
+            let k = 0.34 / (1.34 + (m + 1.0) / (m - 1.0));
+            let rs = bars[1..].iter().map(rogers_satchell).sum::<f64>() / m;
+            sample_variance(&overnight) + k * sample_variance(&open_close) + (1.0 - k) * rs
+        }
+    };
+    Ok((variance.max(0.0) * periods_per_year).sqrt())
+}
+
+/// Annualized volatility estimates over rolling windows of `window` bars, the first estimate is for bars
+/// `0..window`, the last one for the last `window` bars
+pub fn rolling_vol(estimator: VolEstimator, bars: &[Bar], window: usize, periods_per_year: f64) -> Result<Vec<f64>, String> {
+    validate_periods(periods_per_year)?;
+    if window < estimator.min_bars() {
+        return Err(format!("window must have at least {} bars for {:?} estimator", estimator.min_bars(), estimator));
+    }
+    bars.windows(window).map(|w| realized_vol(estimator, w, periods_per_year)).collect()
+}
+
+fn validate_periods(periods_per_year: f64) -> Result<(), String> {
+    if !(periods_per_year.is_finite() && periods_per_year > 0.0) {
+        return Err("periods per year must be positive".to_string());
+    }
+    Ok(())
+}
+
+fn validate(bar: &Bar) -> Result<(), String> {
+    if [bar.open, bar.high, bar.low, bar.close].iter().any(|p| !p.is_finite()) {
+        return Err("prices must be finite".to_string());
+    }
+    if bar.low <= 0.0 {
+        return Err("prices must be positive".to_string());
+    }
+    if bar.high < bar.open.max(bar.close) || bar.low > bar.open.min(bar.close) {
+        return Err("high and low must contain open and close".to_string());
+    }
+    Ok(())
+}
+
+#[inline]
+fn rogers_satchell(bar: &Bar) -> f64 {
+    (bar.high / bar.close).ln() * (bar.high / bar.open).ln() + (bar.low / bar.close).ln() * (bar.low / bar.open).ln()
+}
+
+/// Variance with sample mean and `n - 1` denominator
+#[inline]
+fn sample_variance(xs: &[f64]) -> f64 {
+    let n = xs.len() as f64;
+    let mean = xs.iter().sum::<f64>() / n;
+    xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+}
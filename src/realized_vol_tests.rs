@@ -0,0 +1,102 @@
+#![cfg(test)]
+use rand::prelude::*;
+use rv::prelude::*;
+use crate::realized_vol::*;
+
+const EPS: f64 = 1e-12;
+
+/// Daily bars of geometric Brownian motion sampled `intraday` times per trading session,
+/// `overnight_vol` is annualized volatility of opening jumps
+fn simulate_bars(days: usize, vol: f64, drift: f64, overnight_vol: f64, seed: u64) -> Vec<Bar> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let n: Gaussian = Gaussian::standard();
+    let intraday = 200;
+    let dt = 1.0 / TRADING_DAYS / intraday as f64;
+    let mut close = 100.0;
+    (0..days).map(|_| {
+        let jump: f64 = n.draw(&mut rng);
+        let open = close * (overnight_vol * (1.0 / TRADING_DAYS).sqrt() * jump).exp();
+        let mut bar = Bar { open, high: open, low: open, close: open };
+        for _ in 0..intraday {
+            let z: f64 = n.draw(&mut rng);
+            bar.close *= ((drift - 0.5 * vol * vol) * dt + vol * dt.sqrt() * z).exp();
+            bar.high = bar.high.max(bar.close);
+            bar.low = bar.low.min(bar.close);
+        }
+        close = bar.close;
+        bar
+    }).collect()
+}
+
+const ESTIMATORS: [VolEstimator; 5] = [VolEstimator::CloseToClose, VolEstimator::Parkinson, VolEstimator::GarmanKlass,
+                                        VolEstimator::RogersSatchell, VolEstimator::YangZhang];
+
+#[test]
+fn test_estimators_on_known_bars() {
+    let bars = [Bar { open: 100.0, high: 102.0, low: 99.0, close: 101.0 },
+                Bar { open: 101.0, high: 103.0, low: 100.0, close: 100.0 },
+                Bar { open: 100.5, high: 101.0, low: 98.0, close: 99.0 }];
+    let r = [(100.0f64 / 101.0).ln(), (99.0f64 / 100.0).ln()];
+    let mean = (r[0] + r[1]) / 2.0;
+    let c2c = (r[0] - mean).powi(2) + (r[1] - mean).powi(2);
+    assert!((realized_vol(VolEstimator::CloseToClose, &bars, 1.0).unwrap() - c2c.sqrt()).abs() < EPS);
+
+    let park = ((102.0f64 / 99.0).ln().powi(2) + (103.0f64 / 100.0).ln().powi(2) + (101.0f64 / 98.0).ln().powi(2)) / (12.0 * 2f64.ln());
+    assert!((realized_vol(VolEstimator::Parkinson, &bars, TRADING_DAYS).unwrap() - (park * TRADING_DAYS).sqrt()).abs() < EPS);
+
+    let b = bars[0];
+    let rs = (b.high / b.close).ln() * (b.high / b.open).ln() + (b.low / b.close).ln() * (b.low / b.open).ln();
+    assert!((realized_vol(VolEstimator::RogersSatchell, &bars[..1], 1.0).unwrap() - rs.sqrt()).abs() < EPS);
+    let gk = 0.5 * (b.high / b.low).ln().powi(2) - (2.0 * 2f64.ln() - 1.0) * (b.close / b.open).ln().powi(2);
+    assert!((realized_vol(VolEstimator::GarmanKlass, &bars[..1], 1.0).unwrap() - gk.sqrt()).abs() < EPS);
+}
+
+#[test]
+fn test_estimators_recover_volatility() {
+    let bars = simulate_bars(1000, 0.25, 0.05, 0.0, 17);
+    for estimator in ESTIMATORS.iter() {
+        let vol = realized_vol(*estimator, &bars, TRADING_DAYS).unwrap();
+        // range based estimators are biased low by discrete sampling of high and low
+        assert!((vol - 0.25).abs() < 0.025, "{:?} {}", estimator, vol);
+    }
+
+    // range estimators ignore overnight jumps, close-to-close and Yang-Zhang include them
+    let gaps = simulate_bars(1000, 0.2, 0.0, 0.15, 19);
+    let total = (0.2f64 * 0.2 + 0.15 * 0.15).sqrt();
+    assert!((realized_vol(VolEstimator::CloseToClose, &gaps, TRADING_DAYS).unwrap() - total).abs() < 0.02);
+    assert!((realized_vol(VolEstimator::YangZhang, &gaps, TRADING_DAYS).unwrap() - total).abs() < 0.02);
+    assert!((realized_vol(VolEstimator::RogersSatchell, &gaps, TRADING_DAYS).unwrap() - 0.2).abs() < 0.02);
+}
+
+#[test]
+fn test_rolling_vol() {
+    let bars = simulate_bars(60, 0.3, 0.0, 0.05, 23);
+    for estimator in ESTIMATORS.iter() {
+        let rolling = rolling_vol(*estimator, &bars, 21, TRADING_DAYS).unwrap();
+        assert_eq!(rolling.len(), 40);
+        assert_eq!(rolling[39], realized_vol(*estimator, &bars[39..], TRADING_DAYS).unwrap());
+    }
+    assert!(rolling_vol(VolEstimator::YangZhang, &bars, 2, TRADING_DAYS).is_err());
+    assert!(realized_vol(VolEstimator::CloseToClose, &bars[..2], TRADING_DAYS).is_err());
+    for periods in [0.0, -252.0, f64::NAN, f64::INFINITY] {
+        assert!(realized_vol(VolEstimator::Parkinson, &bars, periods).is_err());
+        assert!(rolling_vol(VolEstimator::Parkinson, &bars[..5], 21, periods).is_err());
+    }
+}
+
+#[test]
+fn test_bars_from_csv() {
+    let lines: Vec<String> = ["date,open,high,low,close", "2024-01-02,100,101,99,100.5", "", "2024-01-03,100.5,102,100,101"]
+        .iter().map(|l| l.to_string()).collect();
+    let bars = bars_from_csv(&lines).unwrap();
+    assert_eq!(bars.len(), 2);
+    assert_eq!(bars[1], Bar { open: 100.5, high: 102.0, low: 100.0, close: 101.0 });
+
+    assert!(bars_from_csv(&["2024-01-02,100,101,99".to_string()]).is_err());
+    assert!(bars_from_csv(&["2024-01-02,100,99.5,99,100.5".to_string()]).is_err());
+    assert!(bars_from_csv(&["2024-01-02,100,101,0,100.5".to_string()]).is_err());
+    assert!(bars_from_csv(&["2024-01-02,nan,101,99,100.5".to_string()]).is_err());
+    assert!(bars_from_csv(&["2024-01-02,100,inf,99,100.5".to_string()]).is_err());
+    let nan = Bar { open: 100.0, high: 101.0, low: 99.0, close: f64::NAN };
+    assert!(realized_vol(VolEstimator::Parkinson, &[nan], TRADING_DAYS).is_err());
+}
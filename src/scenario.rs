@@ -0,0 +1,113 @@
+//! module with scenario analysis (risk ladder): revaluation of option positions over a grid of relative spot shifts,
+//! absolute volatility shifts and time decay. Each scenario is a contract bump in the manner of
+//! [`simulate_call`] and [`simulate_put`], options expiring within the time shift are valued at intrinsic value.
+//!
+//! # Example
+//! ```
+//! use ivol::black_scholes::*;
+//! use ivol::scenario::*;
+//!
+//! let bs_params = BlackScholesParams{price: 100.0, strike: 100.0, div_yield: 0.0, rate: 0.05, vol: 0.2, time_to_expiry: 0.5};
+//! let positions = [OptionPosition{is_call: true, quantity: 10.0, bs_params}];
+//! let grid = ScenarioGrid{spot_shifts: vec![-0.1, 0.0, 0.1], vol_shifts: vec![0.0, 0.05], time_shifts: vec![0.0]};
+//! let ladder = scenario_ladder(&positions, &grid).unwrap();
+//! assert_eq!(ladder.len(), 6);
+//! assert!(ladder[0].pnl < 0.0 && ladder[5].pnl > 0.0);
+//! ```
+use crate::black_scholes::*;
+
+/// Shortest time to expiry of revalued options
+const MIN_TIME_TO_EXPIRY: f64 = 1e-6;
+/// Lowest volatility of revalued options
+const MIN_VOL: f64 = 1e-4;
+
+/// Quantity of call or put options
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptionPosition {
+    pub is_call: bool,
+    /// number of options, negative for short position
+    pub quantity: f64,
+    pub bs_params: BlackScholesParams
+}
+
+/// Scenario grid, each combination of shifts is a scenario
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioGrid {
+    /// relative underlying price shifts, e.g. `-0.05` for 5% drop
+    pub spot_shifts: Vec<f64>,
+    /// absolute volatility shifts, e.g. `0.01` for 1 volatility point up
+    pub vol_shifts: Vec<f64>,
+    /// elapsed time in years, `vec![0.0]` for instantaneous shifts
+    pub time_shifts: Vec<f64>
+}
+
+/// Revaluation of positions in one scenario
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScenarioCell {
+    pub spot_shift: f64,
+    pub vol_shift: f64,
+    pub time_shift: f64,
+    /// change of positions value against current market
+    pub pnl: f64,
+    /// value and greeks of positions in the scenario, weighted by quantities
+    pub greeks: Greeks
+}
+
+/// Contract bump of the scenario
+pub fn scenario_bump(spot_shift: f64, vol_shift: f64, time_shift: f64) -> impl Fn(&BlackScholesParams) -> BlackScholesParams {
+    move |p| BlackScholesParams {
+        price: p.price * (1.0 + spot_shift),
+        vol: (p.vol + vol_shift).max(MIN_VOL),
+        time_to_expiry: (p.time_to_expiry - time_shift).max(MIN_TIME_TO_EXPIRY),
+        ..*p
+    }
+}
+
+/// Value and greeks of option in the scenario, option expiring within the time shift has intrinsic value and zero greeks
+pub fn scenario_greeks(is_call: bool, bs_params: &BlackScholesParams, spot_shift: f64, vol_shift: f64, time_shift: f64) -> Greeks {
+    let p = scenario_bump(spot_shift, vol_shift, time_shift)(bs_params);
+    if bs_params.time_to_expiry <= time_shift {
+        let sign = if is_call {1.0} else {-1.0};
+        return Greeks { premium: (sign * (p.price - p.strike)).max(0.0), ..Greeks::default() };
+    }
+    option_greeks(is_call, &p)
+}
+
+/// Revalues positions in every scenario of the grid. Cells are ordered by time shift, then spot shift, then vol shift.
+/// Fails if some spot shift is not greater than -1 (underlying price must stay positive).
+pub fn scenario_ladder(positions: &[OptionPosition], grid: &ScenarioGrid) -> Result<Vec<ScenarioCell>, String> {
+    if let Some(s) = grid.spot_shifts.iter().find(|s| !(s.is_finite() && **s > -1.0)) {
+        return Err(format!("spot shift {} must be greater than -1", s));
+    }
+    if grid.vol_shifts.iter().chain(grid.time_shifts.iter()).any(|s| !s.is_finite()) {
+        return Err("volatility and time shifts must be finite".to_string());
+    }
+    let mut cells = Vec::with_capacity(grid.spot_shifts.len() * grid.vol_shifts.len() * grid.time_shifts.len());
+    for time_shift in grid.time_shifts.iter() {
+        for spot_shift in grid.spot_shifts.iter() {
+            for vol_shift in grid.vol_shifts.iter() {
+                let mut cell = ScenarioCell {
+                    spot_shift: *spot_shift,
+                    vol_shift: *vol_shift,
+                    time_shift: *time_shift,
+                    pnl: 0.0,
+                    greeks: Greeks::default()
+                };
+                for position in positions.iter() {
+                    let p = &position.bs_params;
+                    let q = position.quantity;
+                    let g = scenario_greeks(position.is_call, p, *spot_shift, *vol_shift, *time_shift);
+                    cell.pnl += q * (g.premium - generic_black_scholes(position.is_call, p));
+                    cell.greeks.premium += q * g.premium;
+                    cell.greeks.delta += q * g.delta;
+                    cell.greeks.gamma += q * g.gamma;
+                    cell.greeks.vega += q * g.vega;
+                    cell.greeks.theta += q * g.theta;
+                    cell.greeks.rho += q * g.rho;
+                }
+                cells.push(cell);
+            }
+        }
+    }
+    Ok(cells)
+}
@@ -0,0 +1,69 @@
+#![cfg(test)]
+use crate::black_scholes::*;
+use crate::scenario::*;
+
+const EPS: f64 = 0.0001;
+
+fn positions() -> Vec<OptionPosition> {
+    let p = BlackScholesParams { price: 100.0, strike: 100.0, rate: 0.05, div_yield: 0.01, vol: 0.2, time_to_expiry: 0.5 };
+    vec![
+        OptionPosition { is_call: true, quantity: 10.0, bs_params: p },
+        OptionPosition { is_call: false, quantity: -5.0, bs_params: BlackScholesParams { strike: 90.0, vol: 0.25, ..p } }
+    ]
+}
+
+fn grid(spot_shifts: &[f64], vol_shifts: &[f64], time_shifts: &[f64]) -> ScenarioGrid {
+    ScenarioGrid { spot_shifts: spot_shifts.to_vec(), vol_shifts: vol_shifts.to_vec(), time_shifts: time_shifts.to_vec() }
+}
+
+#[test]
+fn test_base_scenario() {
+    let positions = positions();
+    let ladder = scenario_ladder(&positions, &grid(&[0.0], &[0.0], &[0.0])).unwrap();
+    assert_eq!(ladder.len(), 1);
+    assert_eq!(ladder[0].pnl, 0.0);
+    let value: f64 = positions.iter().map(|p| p.quantity * option_greeks(p.is_call, &p.bs_params).premium).sum();
+    let delta: f64 = positions.iter().map(|p| p.quantity * option_greeks(p.is_call, &p.bs_params).delta).sum();
+    assert!((ladder[0].greeks.premium - value).abs() < 1e-10);
+    assert!((ladder[0].greeks.delta - delta).abs() < 1e-10);
+}
+
+#[test]
+fn test_ladder_against_greeks() {
+    let positions = positions();
+    let base = scenario_ladder(&positions, &grid(&[0.0], &[0.0], &[0.0])).unwrap()[0].greeks;
+    let ladder = scenario_ladder(&positions, &grid(&[-0.0001, 0.0001], &[-0.0001, 0.0, 0.0001], &[0.0, 0.0001])).unwrap();
+    assert_eq!(ladder.len(), 12);
+
+    // order of cells: time, spot, vol
+    let cell = |t: usize, s: usize, v: usize| ladder[t * 6 + s * 3 + v];
+    assert_eq!((cell(1, 0, 2).time_shift, cell(1, 0, 2).spot_shift, cell(1, 0, 2).vol_shift), (0.0001, -0.0001, 0.0001));
+
+    let ds = 0.0001 * 100.0;
+    assert!(((cell(0, 1, 1).pnl - cell(0, 0, 1).pnl) / (2.0 * ds) - base.delta).abs() < EPS);
+    // vega is per 1% of volatility
+    let vega = (scenario_ladder(&positions, &grid(&[0.0], &[0.0001], &[0.0])).unwrap()[0].pnl
+        - scenario_ladder(&positions, &grid(&[0.0], &[-0.0001], &[0.0])).unwrap()[0].pnl) / 0.0002 * 0.01;
+    assert!((vega - base.vega).abs() < EPS);
+    let decay = scenario_ladder(&positions, &grid(&[0.0], &[0.0], &[0.0001])).unwrap()[0].pnl / 0.0001;
+    assert!((decay - base.theta).abs() < 0.01);
+}
+
+#[test]
+fn test_large_shifts() {
+    // long call and short put gain when spot rises, options expiring within the time shift have intrinsic value only
+    let positions = positions();
+    let ladder = scenario_ladder(&positions, &grid(&[-0.2, 0.2], &[-0.5], &[1.0])).unwrap();
+    assert!(ladder[0].pnl < 0.0 && ladder[1].pnl > 0.0);
+    let expired = 10.0 * 20.0 - 5.0 * 0.0;
+    assert!((ladder[1].greeks.premium - expired).abs() < 1e-12);
+    assert_eq!((ladder[1].greeks.delta, ladder[1].greeks.gamma, ladder[1].greeks.theta), (0.0, 0.0, 0.0));
+    let value: f64 = positions.iter().map(|p| p.quantity * option_greeks(p.is_call, &p.bs_params).premium).sum();
+    assert!((ladder[1].pnl - (expired - value)).abs() < 1e-9);
+
+    // underlying price must stay positive
+    assert!(scenario_ladder(&positions, &grid(&[-1.0, 0.0], &[0.0], &[0.0])).is_err());
+    assert!(scenario_ladder(&positions, &grid(&[0.0], &[f64::NAN], &[0.0])).is_err());
+    let crash = scenario_ladder(&positions, &grid(&[-0.99], &[0.0], &[0.0, 0.5])).unwrap();
+    assert!(crash[0].pnl < 0.0 && (crash[1].greeks.premium + 5.0 * (90.0 - 1.0)).abs() < 1e-9);
+}
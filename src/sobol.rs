@@ -0,0 +1,312 @@
+//! module with Sobol low discrepancy sequence and Brownian bridge path construction for quasi-Monte Carlo pricing.
+//!
+//! # Example
+//! ```
+//! use ivol::black_scholes::*;
+//! use ivol::monte_carlo::*;
+//! use ivol::sobol::*;
+//!
+//! let bs_params = BlackScholesParams{price: 100.0, strike: 100.0, div_yield: 0.0, rate: 0.03, vol: 0.25, time_to_expiry: 1.0};
+//! let mc_params = McParams{paths: 4096, steps: 16, seed: 0, antithetic: false, control_variate: false};
+//!
+//! let mut source = SobolSource::new(mc_params.steps).unwrap();
+//! let qmc = mc_premium_with_source(&bs_params, &mc_params, &mut source, |path| (path[path.len() - 1] - 100.0).max(0.0)).unwrap();
+//! assert!((qmc.premium - call_premium(&bs_params)).abs() < 0.05);
+//! ```
+use rand::prelude::*;
+use rv::prelude::*;
+use crate::monte_carlo::RandomSource;
+
+/// Number of bits of direction numbers
+const BITS: u32 = 32;
+/// Maximum dimension of the sequence with built-in direction numbers, the first dimension and dimensions of Joe/Kuo table.
+/// Higher dimensions need direction numbers loaded with [`parse_direction_numbers`].
+pub const MAX_DIMENSION: usize = JOE_KUO.len() + 1;
+
+/// Primitive polynomials and initial direction numbers from Joe and Kuo (new-joe-kuo-6.21201) for dimensions 2 to 21:
+/// (degree, polynomial coefficients, initial direction numbers)
+const JOE_KUO: [(u32, u64, &[u64]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69])
+];
+
+/// Primitive polynomial and initial direction numbers of one dimension
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectionNumbers {
+    /// degree of the polynomial
+    pub degree: u32,
+    /// coefficients of the polynomial without the leading and trailing ones
+    pub coeffs: u64,
+    /// initial direction numbers `m_1 .. m_degree`
+    pub initial: Vec<u64>
+}
+
+/// Sobol sequence generator using Gray code ordering.
+/// The first point (origin) of the sequence is skipped.
+#[derive(Debug, Clone)]
+pub struct SobolSequence {
+    directions: Vec<[u32; BITS as usize]>,
+    state: Vec<u32>,
+    shift: Vec<u32>,
+    index: u32
+}
+
+impl SobolSequence {
+
+    /// Creates generator of points in `dimension`-dimensional unit cube with built-in direction numbers
+    pub fn new(dimension: usize) -> Result<SobolSequence, String> {
+        let table: Vec<DirectionNumbers> = JOE_KUO.iter()
+            .map(|&(degree, coeffs, initial)| DirectionNumbers { degree, coeffs, initial: initial.to_vec() })
+            .collect();
+        SobolSequence::with_direction_numbers(dimension, &table)
+    }
+
+    /// Creates generator of points in `dimension`-dimensional unit cube with direction numbers of dimensions 2 and higher
+    /// from `table`, e.g. parsed from Joe/Kuo file with [`parse_direction_numbers`]
+    pub fn with_direction_numbers(dimension: usize, table: &[DirectionNumbers]) -> Result<SobolSequence, String> {
+        if dimension == 0 || dimension > table.len() + 1 {
+            return Err(format!("Sobol sequence dimension must be between 1 and {}", table.len() + 1));
+        }
+
+        let mut directions = Vec::with_capacity(dimension);
+        let mut first = [0u32; BITS as usize];
+        for (i, v) in first.iter_mut().enumerate() {
+            *v = 1 << (BITS - 1 - i as u32);
+        }
+        directions.push(first);
+
+        for d in table.iter().take(dimension - 1) {
+            directions.push(direction_numbers(d.degree, d.coeffs, &d.initial));
+        }
+
+        Ok(SobolSequence { directions, state: vec![0; dimension], shift: vec![0; dimension], index: 0 })
+    }
+
+    /// Creates randomized sequence, where each dimension is scrambled with random digital shift generated from `seed`
+    pub fn with_digital_shift(dimension: usize, seed: u64) -> Result<SobolSequence, String> {
+        Ok(SobolSequence::new(dimension)?.digital_shift(seed))
+    }
+
+    /// Scrambles each dimension of the sequence with random digital shift generated from `seed`
+    pub fn digital_shift(mut self, seed: u64) -> SobolSequence {
+        let mut rng = StdRng::seed_from_u64(seed);
+        for s in self.shift.iter_mut() {
+            *s = rng.gen();
+        }
+        self
+    }
+
+    /// Dimension of the sequence points
+    pub fn dimension(&self) -> usize {
+        self.directions.len()
+    }
+
+    /// Writes next point of the sequence into `out`, all coordinates are in open interval (0, 1)
+    pub fn next_point(&mut self, out: &mut [f64]) {
+        // Gray code: flip direction number of the rightmost zero bit of the index
+        let c = (!self.index).trailing_zeros() as usize;
+        self.index += 1;
+        for (j, x) in out.iter_mut().enumerate().take(self.directions.len()) {
+            self.state[j] ^= self.directions[j][c];
+            *x = ((self.state[j] ^ self.shift[j]) as f64 + 0.5) / 2f64.powi(BITS as i32);
+        }
+    }
+}
+
+/// Parses direction numbers from lines `d s a m_1 .. m_s` of Joe/Kuo files (e.g. new-joe-kuo-6.21201 published
+/// at <https://web.maths.unsw.edu.au/~fkuo/sobol/>), where `d` is dimension starting from 2, `s` degree and `a` coefficients
+/// of primitive polynomial. Header and empty lines are skipped.
+pub fn parse_direction_numbers(lines: &[String]) -> Result<Vec<DirectionNumbers>, String> {
+    let mut table = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.is_empty() || (i == 0 && fields[0].parse::<u64>().is_err()) {
+            continue;
+        }
+        let numbers = fields.iter().map(|f| f.parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>()
+            .map_err(|_| format!("line {}: expected non-negative integers", i + 1))?;
+        if numbers.len() < 3 || numbers[0] != table.len() as u64 + 2 {
+            return Err(format!("line {}: expected dimension {} followed by degree and coefficients", i + 1, table.len() + 2));
+        }
+        let (degree, coeffs, initial) = (numbers[1], numbers[2], &numbers[3..]);
+        if degree == 0 || degree >= BITS as u64 || coeffs >= 1 << (degree - 1) || initial.len() != degree as usize {
+            return Err(format!("line {}: expected degree between 1 and {} with coefficients and degree initial numbers", i + 1, BITS - 1));
+        }
+        // m_k must be odd and less than 2^k
+        if initial.iter().enumerate().any(|(k, m)| m % 2 == 0 || *m >= 1 << (k + 1)) {
+            return Err(format!("line {}: initial direction numbers m_k must be odd and less than 2^k", i + 1));
+        }
+        table.push(DirectionNumbers { degree: degree as u32, coeffs, initial: initial.to_vec() });
+    }
+    Ok(table)
+}
+
+/// Direction numbers `v_i = m_i / 2^i` scaled to `BITS` bits, with `m_i` extended by Sobol recurrence
+fn direction_numbers(degree: u32, coeffs: u64, initial: &[u64]) -> [u32; BITS as usize] {
+    let s = degree as usize;
+    let mut m = vec![0u64; BITS as usize];
+    m[..s].copy_from_slice(initial);
+    for i in s..BITS as usize {
+        let mut v = m[i - s] ^ (m[i - s] << s);
+        for k in 1..s {
+            if (coeffs >> (s - 1 - k)) & 1 == 1 {
+                v ^= m[i - k] << k;
+            }
+        }
+        m[i] = v;
+    }
+
+    let mut dirs = [0u32; BITS as usize];
+    for (i, v) in dirs.iter_mut().enumerate() {
+        *v = (m[i] << (BITS as usize - 1 - i)) as u32;
+    }
+    dirs
+}
+
+/// Brownian bridge over equally spaced time steps. Transforms independent normals ordered by importance
+/// (first one defines terminal value, next ones the midpoints) into normalized Brownian increments.
+#[derive(Debug, Clone)]
+pub struct BrownianBridge {
+    bridge_index: Vec<usize>,
+    left_index: Vec<usize>,
+    right_index: Vec<usize>,
+    left_weight: Vec<f64>,
+    right_weight: Vec<f64>,
+    std_dev: Vec<f64>,
+    path: Vec<f64>
+}
+
+impl BrownianBridge {
+
+    pub fn new(steps: usize) -> BrownianBridge {
+        let mut bridge = BrownianBridge {
+            bridge_index: vec![0; steps],
+            left_index: vec![0; steps],
+            right_index: vec![0; steps],
+            left_weight: vec![0.0; steps],
+            right_weight: vec![0.0; steps],
+            std_dev: vec![0.0; steps],
+            path: vec![0.0; steps]
+        };
+        if steps == 0 {
+            return bridge;
+        }
+
+        // times are measured in steps: point l corresponds to time l + 1
+        let time = |i: usize| (i + 1) as f64;
+        let mut map = vec![0usize; steps];
+        map[steps - 1] = 1;
+        bridge.bridge_index[0] = steps - 1;
+        bridge.std_dev[0] = time(steps - 1).sqrt();
+
+        let mut j = 0;
+        for i in 1..steps {
+            while map[j] != 0 {
+                j += 1;
+            }
+            let mut k = j;
+            while map[k] == 0 {
+                k += 1;
+            }
+            let l = j + ((k - 1 - j) >> 1);
+            map[l] = i;
+            bridge.bridge_index[i] = l;
+            bridge.left_index[i] = j;
+            bridge.right_index[i] = k;
+
+            let t_left = if j == 0 {0.0} else {time(j - 1)};
+            let (t_l, t_k) = (time(l), time(k));
+            bridge.left_weight[i] = (t_k - t_l) / (t_k - t_left);
+            bridge.right_weight[i] = (t_l - t_left) / (t_k - t_left);
+            bridge.std_dev[i] = ((t_l - t_left) * (t_k - t_l) / (t_k - t_left)).sqrt();
+
+            j = k + 1;
+            if j >= steps {
+                j = 0;
+            }
+        }
+        bridge
+    }
+
+    /// Converts normals `z` into normalized Brownian increments written into `out`
+    pub fn transform(&mut self, z: &[f64], out: &mut [f64]) {
+        let steps = self.path.len();
+        if steps == 0 {
+            return;
+        }
+        self.path[steps - 1] = self.std_dev[0] * z[0];
+        for (i, zi) in z.iter().enumerate().take(steps).skip(1) {
+            let (j, k, l) = (self.left_index[i], self.right_index[i], self.bridge_index[i]);
+            let left = if j == 0 {0.0} else {self.left_weight[i] * self.path[j - 1]};
+            self.path[l] = left + self.right_weight[i] * self.path[k] + self.std_dev[i] * zi;
+        }
+
+        let mut prev = 0.0;
+        for (dw, w) in out.iter_mut().zip(self.path.iter()) {
+            *dw = w - prev;
+            prev = *w;
+        }
+    }
+}
+
+/// Quasi random source of normals for [`crate::monte_carlo::mc_premium_with_source`]: Sobol points are mapped
+/// to normals with inverse normal CDF and turned into path increments with Brownian bridge.
+/// Standard error reported by the engine is the pseudo random estimate and is usually conservative for Sobol points.
+pub struct SobolSource {
+    sequence: SobolSequence,
+    bridge: BrownianBridge,
+    n: Gaussian,
+    point: Vec<f64>
+}
+
+impl SobolSource {
+
+    /// Creates source for paths with `steps` time steps
+    pub fn new(steps: usize) -> Result<SobolSource, String> {
+        Ok(SobolSource::from_sequence(SobolSequence::new(steps)?))
+    }
+
+    /// Creates source with randomly shifted Sobol sequence, so that independent estimates can be produced with different seeds
+    pub fn with_digital_shift(steps: usize, seed: u64) -> Result<SobolSource, String> {
+        Ok(SobolSource::from_sequence(SobolSequence::with_digital_shift(steps, seed)?))
+    }
+
+    /// Creates source from sequence, e.g. with loaded direction numbers, whose dimension is the number of time steps
+    pub fn from_sequence(sequence: SobolSequence) -> SobolSource {
+        let steps = sequence.dimension();
+        SobolSource { sequence, bridge: BrownianBridge::new(steps), n: Gaussian::standard(), point: vec![0.0; steps] }
+    }
+}
+
+impl RandomSource for SobolSource {
+    fn next_normals(&mut self, out: &mut [f64]) {
+        self.sequence.next_point(&mut self.point);
+        for u in self.point.iter_mut() {
+            *u = self.n.invcdf(*u);
+        }
+        self.bridge.transform(&self.point, out);
+    }
+
+    fn dimension(&self) -> Option<usize> {
+        Some(self.point.len())
+    }
+}
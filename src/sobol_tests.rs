@@ -0,0 +1,121 @@
+#![cfg(test)]
+use crate::black_scholes;
+use crate::black_scholes::BlackScholesParams;
+use crate::monte_carlo::*;
+use crate::sobol::*;
+
+#[test]
+fn test_first_dimension_is_van_der_corput() {
+    let mut seq = SobolSequence::new(1).unwrap();
+    let mut point = [0.0];
+    let expected = [0.5, 0.75, 0.25, 0.375, 0.875, 0.625, 0.125];
+    for e in expected.iter() {
+        seq.next_point(&mut point);
+        assert!((point[0] - e).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_stratification() {
+    // first 2^k points of every dimension hit each dyadic interval of length 2^-k exactly once
+    // (origin is skipped, so the block starts from index 1 and ends with point 2^k, which is also stratified)
+    let dimension = MAX_DIMENSION;
+    let k = 8;
+    let mut seq = SobolSequence::new(dimension).unwrap();
+    let mut point = vec![0.0; dimension];
+    let mut counts = vec![vec![0; 1 << k]; dimension];
+    for _ in 0..(1 << k) - 1 {
+        seq.next_point(&mut point);
+        for (d, x) in point.iter().enumerate() {
+            counts[d][(x * (1 << k) as f64) as usize] += 1;
+        }
+    }
+    for c in counts.iter() {
+        // only the origin cell is empty
+        assert_eq!(c[0], 0);
+        assert!(c[1..].iter().all(|n| *n == 1));
+    }
+}
+
+#[test]
+fn test_invalid_dimension() {
+    assert!(SobolSequence::new(0).is_err());
+    assert!(SobolSequence::new(MAX_DIMENSION + 1).is_err());
+    assert!(SobolSequence::new(MAX_DIMENSION).is_ok());
+}
+
+#[test]
+fn test_parse_direction_numbers() {
+    // the first dimensions of new-joe-kuo-6.21201 give the built-in sequence
+    let lines: Vec<String> = ["d       s       a       m_i", "2       1       0       1", "3       2       1       1 3",
+        "4       3       1       1 3 1", "5       3       2       1 1 1", "6       4       1       1 1 3 3"]
+        .iter().map(|l| l.to_string()).collect();
+    let table = parse_direction_numbers(&lines).unwrap();
+    assert_eq!(table[3], DirectionNumbers { degree: 3, coeffs: 2, initial: vec![1, 1, 1] });
+
+    let mut loaded = SobolSequence::with_direction_numbers(6, &table).unwrap();
+    let mut builtin = SobolSequence::new(6).unwrap();
+    let (mut a, mut b) = ([0.0; 6], [0.0; 6]);
+    for _ in 0..100 {
+        loaded.next_point(&mut a);
+        builtin.next_point(&mut b);
+        assert_eq!(a, b);
+    }
+    assert!(SobolSequence::with_direction_numbers(7, &table).is_err());
+
+    let invalid = |line: &str| parse_direction_numbers(&[line.to_string()]).is_err();
+    // wrong dimension, even or too large initial numbers, missing numbers and coefficients above degree
+    assert!(invalid("3 1 0 1"));
+    assert!(invalid("2 2 1 1 2"));
+    assert!(invalid("2 2 1 1 5"));
+    assert!(invalid("2 2 1 1"));
+    assert!(invalid("2 2 2 1 3"));
+    assert!(invalid("2 1 0 x"));
+}
+
+#[test]
+fn test_brownian_bridge_increments() {
+    let steps = 12;
+    let mut bridge = BrownianBridge::new(steps);
+    let mut source = PseudoRandom::new(3);
+    let mut z = vec![0.0; steps];
+    let mut dw = vec![0.0; steps];
+    let samples = 20000;
+    let mut sum_sq = vec![0.0; steps];
+    let mut cross = 0.0;
+    for _ in 0..samples {
+        source.next_normals(&mut z);
+        bridge.transform(&z, &mut dw);
+        for i in 0..steps {
+            sum_sq[i] += dw[i] * dw[i];
+        }
+        cross += dw[0] * dw[steps - 1];
+    }
+    for s in sum_sq.iter() {
+        assert!((s / samples as f64 - 1.0).abs() < 0.05);
+    }
+    assert!((cross / samples as f64).abs() < 0.05);
+}
+
+#[test]
+fn test_qmc_beats_pseudo_random() {
+    let bs_params = BlackScholesParams { price: 100.0, strike: 110.0, rate: 0.02, div_yield: 0.0, vol: 0.3, time_to_expiry: 1.0 };
+    let mc_params = McParams { paths: 4096, steps: 16, seed: 5, antithetic: false, control_variate: false };
+    let payoff = |path: &[f64]| (path[path.len() - 1] - 110.0).max(0.0);
+    let call = black_scholes::call_premium(&bs_params);
+
+    let mut sobol = SobolSource::new(mc_params.steps).unwrap();
+    let qmc = mc_premium_with_source(&bs_params, &mc_params, &mut sobol, payoff).unwrap();
+    let mc = mc_premium(&bs_params, &mc_params, payoff);
+    assert!((qmc.premium - call).abs() < 0.05);
+    assert!((qmc.premium - call).abs() < 0.2 * mc.std_error);
+
+    let mut shifted = SobolSource::with_digital_shift(mc_params.steps, 1).unwrap();
+    let rqmc = mc_premium_with_source(&bs_params, &mc_params, &mut shifted, payoff).unwrap();
+    assert!((rqmc.premium - call).abs() < 0.05);
+
+    // source must be built for the number of time steps
+    let mut mismatched = SobolSource::new(8).unwrap();
+    assert!(mc_premium_with_source(&bs_params, &mc_params, &mut mismatched, payoff).is_err());
+    assert!(mc_premium_with_source(&bs_params, &mc_params, &mut PseudoRandom::new(1), payoff).is_ok());
+}
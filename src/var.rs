@@ -0,0 +1,230 @@
+//! module with Value-at-Risk and expected shortfall of [`Portfolio`] over market scenarios of relative
+//! underlying price returns and absolute volatility shifts. Scenarios come from history or are simulated
+//! with Monte Carlo, positions are revalued in full or with delta-gamma-vega approximation.
+//! Losses are reported as positive numbers together with contributions of positions, which sum up to the totals.
+//!
+//! # Example
+//! ```
+//! use ivol::portfolio::*;
+//! use ivol::var::*;
+//!
+//! let mut portfolio = Portfolio::new();
+//! portfolio.set_market("SPX", MarketData{price: 4800.0, rate: 0.05, div_yield: 0.015});
+//! let put = Instrument::Option{is_call: false, strike: 4600.0, time_to_expiry: 0.25, vol: 0.18};
+//! portfolio.add_position(Position{underlying: "SPX".to_string(), instrument: put, quantity: -10.0, multiplier: 100.0});
+//!
+//! let factors = [RiskFactor{underlying: "SPX".to_string(), kind: FactorKind::Spot, vol: 0.18},
+//!                RiskFactor{underlying: "SPX".to_string(), kind: FactorKind::Vol, vol: 0.8}];
+//! let correlations = vec![vec![1.0, -0.7], vec![-0.7, 1.0]];
+//! let scenarios = monte_carlo_scenarios(&factors, &correlations, 1.0 / 252.0, 5000, 7).unwrap();
+//! let results = portfolio_var(&portfolio, &scenarios, 1.0 / 252.0, Revaluation::Full, &[0.95, 0.99]).unwrap();
+//! assert!(results[1].var > results[0].var);
+//! assert!(results[1].expected_shortfall >= results[1].var);
+//! ```
+use std::collections::BTreeMap;
+use crate::black_scholes::*;
+use crate::math::cholesky;
+use crate::monte_carlo::*;
+use crate::portfolio::*;
+use crate::scenario::scenario_greeks;
+
+/// Tolerance of tail size rounding, so that e.g. 95% of 100 scenarios has tail of 5 scenarios
+const TAIL_ROUNDING: f64 = 1e-9;
+
+/// Change of market data of one underlying
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MarketShock {
+    /// relative underlying price change, e.g. `-0.05` for 5% drop
+    pub spot_return: f64,
+    /// absolute change of volatilities of all options on the underlying
+    pub vol_shift: f64
+}
+
+/// Market scenario, underlyings without shock are unchanged
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RiskScenario {
+    /// scenario name, e.g. date of historical returns
+    pub label: String,
+    pub shocks: BTreeMap<String, MarketShock>
+}
+
+/// Kind of simulated risk factor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FactorKind {
+    /// underlying price, simulated as geometric Brownian motion
+    Spot,
+    /// volatility level, simulated as arithmetic Brownian motion
+    Vol
+}
+
+/// Risk factor of Monte Carlo scenarios
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskFactor {
+    pub underlying: String,
+    pub kind: FactorKind,
+    /// annualized volatility of the factor, absolute for volatility factors (volatility of volatility)
+    pub vol: f64
+}
+
+/// Revaluation of positions in scenarios
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Revaluation {
+    /// full revaluation with Black/Scholes formula
+    Full,
+    /// second order approximation with delta, gamma, vega and theta
+    DeltaGammaVega
+}
+
+/// Value-at-Risk at one confidence level
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarResult {
+    pub confidence: f64,
+    /// loss not exceeded with probability `confidence`
+    pub var: f64,
+    /// average loss in scenarios at or beyond VaR
+    pub expected_shortfall: f64,
+    /// index of the scenario defining VaR
+    pub var_scenario: usize,
+    /// losses of positions in VaR scenario, in the order of portfolio positions
+    pub var_contributions: Vec<f64>,
+    /// average losses of positions in tail scenarios, in the order of portfolio positions
+    pub es_contributions: Vec<f64>
+}
+
+impl RiskScenario {
+
+    /// Shock of the underlying, zero if scenario does not move it
+    pub fn shock(&self, underlying: &str) -> MarketShock {
+        self.shocks.get(underlying).copied().unwrap_or_default()
+    }
+}
+
+/// Parses historical scenarios from CSV lines `label,underlying,spot_return,vol_shift` (e.g. `2020-03-16,SPX,-0.12,0.2`),
+/// lines with the same label make one scenario. Scenarios keep the order of their first line,
+/// header and empty lines are skipped.
+pub fn historical_scenarios(lines: &[String]) -> Result<Vec<RiskScenario>, String> {
+    let mut scenarios: Vec<RiskScenario> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim().trim_matches('"')).collect();
+        if line.trim().is_empty() || (i == 0 && fields.len() > 2 && fields[2].parse::<f64>().is_err()) {
+            continue;
+        }
+        if fields.len() != 4 {
+            return Err(format!("line {}: expected 4 fields, found {}", i + 1, fields.len()));
+        }
+        let number = |j: usize, name: &str| fields[j].parse::<f64>()
+            .map_err(|_| format!("line {}: {} is not a number: '{}'", i + 1, name, fields[j]));
+        let shock = MarketShock { spot_return: number(2, "spot return")?, vol_shift: number(3, "vol shift")? };
+        if shock.spot_return <= -1.0 {
+            return Err(format!("line {}: spot return must be greater than -1", i + 1));
+        }
+        match scenarios.iter_mut().find(|s| s.label == fields[0]) {
+            Some(scenario) => { scenario.shocks.insert(fields[1].to_string(), shock); },
+            None => {
+                let mut scenario = RiskScenario { label: fields[0].to_string(), ..RiskScenario::default() };
+                scenario.shocks.insert(fields[1].to_string(), shock);
+                scenarios.push(scenario);
+            }
+        }
+    }
+    Ok(scenarios)
+}
+
+/// Simulates scenarios over `horizon` years of correlated risk factors
+/// * correlations - correlation matrix of factors
+pub fn monte_carlo_scenarios(factors: &[RiskFactor], correlations: &[Vec<f64>], horizon: f64, scenarios: usize, seed: u64) -> Result<Vec<RiskScenario>, String> {
+    if factors.is_empty() || factors.len() != correlations.len() || correlations.iter().any(|row| row.len() != factors.len()) {
+        return Err("factors and correlation matrix must have the same dimension".to_string());
+    }
+    let l = cholesky(correlations).ok_or_else(|| "correlation matrix is not positive definite".to_string())?;
+    let mut source = PseudoRandom::new(seed);
+    let mut z = vec![0.0; factors.len()];
+
+    let mut result = Vec::with_capacity(scenarios);
+    for i in 0..scenarios {
+        source.next_normals(&mut z);
+        let mut scenario = RiskScenario { label: (i + 1).to_string(), ..RiskScenario::default() };
+        for (row, factor) in l.iter().zip(factors.iter()) {
+            let x: f64 = row.iter().zip(z.iter()).map(|(a, b)| a * b).sum();
+            let sd = factor.vol * horizon.sqrt();
+            let shock = scenario.shocks.entry(factor.underlying.clone()).or_default();
+            match factor.kind {
+                FactorKind::Spot => shock.spot_return = (sd * x - 0.5 * sd * sd).exp() - 1.0,
+                FactorKind::Vol => shock.vol_shift = sd * x
+            }
+        }
+        result.push(scenario);
+    }
+    Ok(result)
+}
+
+/// P&L of each position (columns) in each scenario (rows) after `horizon` years
+pub fn scenario_pnls(portfolio: &Portfolio, scenarios: &[RiskScenario], horizon: f64, revaluation: Revaluation) -> Result<Vec<Vec<f64>>, String> {
+    let risks = portfolio.position_risks()?;
+    scenarios.iter().map(|scenario| {
+        portfolio.positions().iter().zip(risks.iter()).map(|(position, risk)| {
+            let market = portfolio.market(&position.underlying).ok_or_else(|| format!("no market data for {}", position.underlying))?;
+            let shock = scenario.shock(&position.underlying);
+            let pnl = match revaluation {
+                Revaluation::Full => full_pnl(position, market, &shock, horizon),
+                Revaluation::DeltaGammaVega => {
+                    // dollar gamma is delta change for 1% move, vega for 1 volatility point
+                    let r = shock.spot_return;
+                    risk.dollar_delta * r + 50.0 * risk.dollar_gamma * r * r + risk.vega * 100.0 * shock.vol_shift + risk.theta * horizon
+                }
+            };
+            Ok(pnl)
+        }).collect()
+    }).collect()
+}
+
+/// VaR and expected shortfall at given confidence levels from P&L of positions in scenarios (see [`scenario_pnls`]).
+/// Tail at confidence `c` consists of `ceil((1 - c) * N)` worst of `N` scenarios, VaR is the loss in the best of them.
+pub fn value_at_risk(pnls: &[Vec<f64>], confidences: &[f64]) -> Result<Vec<VarResult>, String> {
+    if pnls.is_empty() {
+        return Err("no scenarios".to_string());
+    }
+    if let Some(c) = confidences.iter().find(|c| !(0.0..1.0).contains(*c)) {
+        return Err(format!("confidence level {} is not in [0, 1)", c));
+    }
+    let totals: Vec<f64> = pnls.iter().map(|row| row.iter().sum()).collect();
+    let mut order: Vec<usize> = (0..pnls.len()).collect();
+    order.sort_by(|&a, &b| totals[a].partial_cmp(&totals[b]).unwrap_or(std::cmp::Ordering::Equal));
+    let positions = pnls[0].len();
+
+    Ok(confidences.iter().map(|&confidence| {
+        let tail = (((1.0 - confidence) * pnls.len() as f64 - TAIL_ROUNDING).ceil() as usize).max(1);
+        let var_scenario = order[tail - 1];
+        let mut es_contributions = vec![0.0; positions];
+        for &s in order[..tail].iter() {
+            for (c, pnl) in es_contributions.iter_mut().zip(pnls[s].iter()) {
+                *c -= pnl / tail as f64;
+            }
+        }
+        VarResult {
+            confidence,
+            var: -totals[var_scenario],
+            expected_shortfall: es_contributions.iter().sum(),
+            var_scenario,
+            var_contributions: pnls[var_scenario].iter().map(|pnl| -pnl).collect(),
+            es_contributions
+        }
+    }).collect())
+}
+
+/// VaR and expected shortfall of portfolio over scenarios at given confidence levels
+pub fn portfolio_var(portfolio: &Portfolio, scenarios: &[RiskScenario], horizon: f64, revaluation: Revaluation, confidences: &[f64]) -> Result<Vec<VarResult>, String> {
+    value_at_risk(&scenario_pnls(portfolio, scenarios, horizon, revaluation)?, confidences)
+}
+
+/// P&L of position with full revaluation in shocked market after `horizon` years
+fn full_pnl(position: &Position, market: &MarketData, shock: &MarketShock, horizon: f64) -> f64 {
+    let units = position.quantity * position.multiplier;
+    match (position.instrument, position.bs_params(market)) {
+        (Instrument::Option { is_call, .. }, Some(p)) => {
+            let shocked = scenario_greeks(is_call, &p, shock.spot_return, shock.vol_shift, horizon).premium;
+            units * (shocked - generic_black_scholes(is_call, &p))
+        },
+        _ => units * market.price * shock.spot_return
+    }
+}
@@ -0,0 +1,102 @@
+#![cfg(test)]
+use crate::portfolio::*;
+use crate::var::*;
+
+const EPS: f64 = 1e-9;
+
+fn portfolio() -> Portfolio {
+    let mut portfolio = Portfolio::new();
+    portfolio.set_market("XYZ", MarketData { price: 100.0, rate: 0.04, div_yield: 0.01 });
+    portfolio.set_market("ABC", MarketData { price: 50.0, rate: 0.04, div_yield: 0.0 });
+    let put = Instrument::Option { is_call: false, strike: 95.0, time_to_expiry: 0.25, vol: 0.25 };
+    let call = Instrument::Option { is_call: true, strike: 55.0, time_to_expiry: 0.5, vol: 0.3 };
+    portfolio.add_position(Position { underlying: "XYZ".to_string(), instrument: put, quantity: -20.0, multiplier: 100.0 });
+    portfolio.add_position(Position { underlying: "XYZ".to_string(), instrument: Instrument::Underlying, quantity: -500.0, multiplier: 1.0 });
+    portfolio.add_position(Position { underlying: "ABC".to_string(), instrument: call, quantity: 15.0, multiplier: 100.0 });
+    portfolio
+}
+
+#[test]
+fn test_historical_scenarios() {
+    let lines: Vec<String> = ["date,underlying,spot_return,vol_shift", "2020-03-12,XYZ,-0.09,0.15", "2020-03-12,ABC,-0.05,0.1",
+                              "", "2020-03-13,XYZ,0.09,-0.08"].iter().map(|l| l.to_string()).collect();
+    let scenarios = historical_scenarios(&lines).unwrap();
+    assert_eq!(scenarios.len(), 2);
+    assert_eq!(scenarios[0].label, "2020-03-12");
+    assert_eq!(scenarios[0].shock("ABC"), MarketShock { spot_return: -0.05, vol_shift: 0.1 });
+    assert_eq!(scenarios[1].shock("ABC"), MarketShock::default());
+
+    assert!(historical_scenarios(&["2020-03-12,XYZ,-0.09".to_string()]).is_err());
+    assert!(historical_scenarios(&["2020-03-12,XYZ,-0.09,0.1".to_string(), "2020-03-13,XYZ,x,0.1".to_string()]).is_err());
+    assert!(historical_scenarios(&["2020-03-12,XYZ,-1.5,0.1".to_string()]).is_err());
+}
+
+#[test]
+fn test_var_and_contributions() {
+    // 100 scenarios with total losses 1..100 split between two positions
+    let pnls: Vec<Vec<f64>> = (1..=100).map(|i| vec![-0.75 * i as f64, -0.25 * i as f64]).collect();
+    let results = value_at_risk(&pnls, &[0.95, 0.99]).unwrap();
+    assert_eq!(results[0].var_scenario, 95);
+    assert!((results[0].var - 96.0).abs() < EPS);
+    // average of 96..100
+    assert!((results[0].expected_shortfall - 98.0).abs() < EPS);
+    assert!((results[0].es_contributions[0] - 0.75 * 98.0).abs() < EPS);
+    assert!((results[0].var_contributions.iter().sum::<f64>() - results[0].var).abs() < EPS);
+    assert!((results[1].var - 100.0).abs() < EPS && (results[1].expected_shortfall - 100.0).abs() < EPS);
+
+    assert!(value_at_risk(&[], &[0.99]).is_err());
+    assert!(value_at_risk(&pnls, &[1.0]).is_err());
+}
+
+#[test]
+fn test_delta_gamma_vega_approximation() {
+    let portfolio = portfolio();
+    let lines: Vec<String> = ["1,XYZ,-0.01,0.005", "1,ABC,0.01,-0.005", "2,XYZ,0.005,0.0", "3,ABC,-0.02,0.01"]
+        .iter().map(|l| l.to_string()).collect();
+    let scenarios = historical_scenarios(&lines).unwrap();
+    let full = scenario_pnls(&portfolio, &scenarios, 1.0 / 252.0, Revaluation::Full).unwrap();
+    let approx = scenario_pnls(&portfolio, &scenarios, 1.0 / 252.0, Revaluation::DeltaGammaVega).unwrap();
+    // cross gamma of spot and volatility is not in approximation
+    for (f, a) in full.iter().zip(approx.iter()) {
+        // underlying positions are linear
+        assert!((f[1] - a[1]).abs() < EPS);
+        assert!((f[0] - a[0]).abs() < 0.05 * f[0].abs().max(1.0));
+        assert!((f[2] - a[2]).abs() < 0.05 * f[2].abs().max(1.0));
+    }
+    assert_eq!(full[1][2], scenario_pnls(&portfolio, &scenarios[1..2], 1.0 / 252.0, Revaluation::Full).unwrap()[0][2]);
+
+    let mut missing = portfolio.clone();
+    missing.add_position(Position { underlying: "N/A".to_string(), instrument: Instrument::Underlying, quantity: 1.0, multiplier: 1.0 });
+    assert!(scenario_pnls(&missing, &scenarios, 0.0, Revaluation::Full).is_err());
+}
+
+#[test]
+fn test_monte_carlo_scenarios() {
+    let factors = [RiskFactor { underlying: "XYZ".to_string(), kind: FactorKind::Spot, vol: 0.25 },
+                   RiskFactor { underlying: "XYZ".to_string(), kind: FactorKind::Vol, vol: 0.5 },
+                   RiskFactor { underlying: "ABC".to_string(), kind: FactorKind::Spot, vol: 0.3 }];
+    let correlations = vec![vec![1.0, -0.6, 0.5], vec![-0.6, 1.0, 0.0], vec![0.5, 0.0, 1.0]];
+    let horizon = 10.0 / 252.0;
+    let scenarios = monte_carlo_scenarios(&factors, &correlations, horizon, 20000, 11).unwrap();
+    assert_eq!(scenarios.len(), 20000);
+
+    let log_returns: Vec<f64> = scenarios.iter().map(|s| (1.0 + s.shock("XYZ").spot_return).ln()).collect();
+    let vol_shifts: Vec<f64> = scenarios.iter().map(|s| s.shock("XYZ").vol_shift).collect();
+    let n = scenarios.len() as f64;
+    let sd = (log_returns.iter().map(|x| x * x).sum::<f64>() / n).sqrt();
+    assert!((sd - 0.25 * horizon.sqrt()).abs() < 0.02 * 0.25 * horizon.sqrt());
+    let cov = log_returns.iter().zip(vol_shifts.iter()).map(|(x, y)| x * y).sum::<f64>() / n;
+    assert!((cov / (0.25 * 0.5 * horizon) + 0.6).abs() < 0.03);
+
+    // full revaluation VaR is close to delta-gamma-vega VaR, expected shortfall is beyond VaR
+    let portfolio = portfolio();
+    let full = portfolio_var(&portfolio, &scenarios, horizon, Revaluation::Full, &[0.99]).unwrap();
+    let approx = portfolio_var(&portfolio, &scenarios, horizon, Revaluation::DeltaGammaVega, &[0.99]).unwrap();
+    assert!(full[0].var > 0.0 && full[0].expected_shortfall > full[0].var);
+    assert!((full[0].var - approx[0].var).abs() < 0.1 * full[0].var);
+    assert!((full[0].es_contributions.iter().sum::<f64>() - full[0].expected_shortfall).abs() < 1e-6);
+
+    let singular = vec![vec![1.0, 1.0, 0.0], vec![1.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]];
+    assert!(monte_carlo_scenarios(&factors, &singular, horizon, 10, 1).is_err());
+    assert!(monte_carlo_scenarios(&factors, &correlations[..2], horizon, 10, 1).is_err());
+}
@@ -0,0 +1,84 @@
+//! module with variance swap fair strike by static replication with a strip of out-of-the-money options
+//! (Carr and Madan, Demeterfi et al.) and volatility swap strike with convexity adjustment.
+//! Fair strikes are annualized variance (squared volatility), the usual quote in volatility points is
+//! `100 * sqrt(variance)`.
+//!
+//! # Example
+//! ```
+//! use ivol::black_scholes::*;
+//! use ivol::variance_swap::*;
+//!
+//! let bs_params = BlackScholesParams{price: 100.0, strike: 0.0, div_yield: 0.01, rate: 0.03, vol: 0.2, time_to_expiry: 0.5};
+//! let strikes: Vec<f64> = (10..=400).map(|k| k as f64 * 0.5).collect();
+//! let strip = OptionStrip::from_model(&bs_params, &strikes, |_| 0.2).unwrap();
+//! assert!((variance_swap_strike(&bs_params, &strip) - 0.04).abs() < 0.0001);
+//! ```
+use crate::black_scholes::*;
+
+/// Strip of call and put premiums on increasing strikes with the same expiry
+#[derive(Debug, Clone)]
+pub struct OptionStrip {
+    pub strikes: Vec<f64>,
+    pub put_premiums: Vec<f64>,
+    pub call_premiums: Vec<f64>
+}
+
+impl OptionStrip {
+
+    /// Strip from market quotes
+    pub fn new(strikes: &[f64], put_premiums: &[f64], call_premiums: &[f64]) -> Result<OptionStrip, String> {
+        if strikes.len() < 2 || strikes.len() != put_premiums.len() || strikes.len() != call_premiums.len() {
+            return Err("strip needs at least two strikes with put and call premium for each".to_string());
+        }
+        if strikes[0] <= 0.0 || strikes.windows(2).any(|w| w[1] <= w[0]) {
+            return Err("strikes must be positive and increasing".to_string());
+        }
+        Ok(OptionStrip { strikes: strikes.to_vec(), put_premiums: put_premiums.to_vec(), call_premiums: call_premiums.to_vec() })
+    }
+
+    /// Strip priced with Black/Scholes formulas, where `vol` gives implied volatility for a strike
+    /// (e.g. from a fitted smile or [`crate::local_vol::ImpliedVolSurface`])
+    pub fn from_model<F>(bs_params: &BlackScholesParams, strikes: &[f64], vol: F) -> Result<OptionStrip, String>
+        where F: Fn(f64) -> f64 {
+        let params: Vec<BlackScholesParams> = strikes.iter().map(|k| BlackScholesParams { strike: *k, vol: vol(*k), ..*bs_params }).collect();
+        let puts: Vec<f64> = params.iter().map(put_premium).collect();
+        let calls: Vec<f64> = params.iter().map(call_premium).collect();
+        OptionStrip::new(strikes, &puts, &calls)
+    }
+}
+
+/// Fair strike of continuously monitored variance swap replicated with out-of-the-money options of the strip.
+/// Puts are used below the last strike not above the forward, calls above it. Variance from strikes
+/// beyond the strip is ignored, so the strip should cover the tails of the distribution.
+/// Strike and volatility of `bs_params` are not used.
+pub fn variance_swap_strike(bs_params: &BlackScholesParams, strip: &OptionStrip) -> f64 {
+    let t = bs_params.time_to_expiry;
+    let forward = bs_params.price * ((bs_params.rate - bs_params.div_yield) * t).exp();
+    let strikes = &strip.strikes;
+    let last = strikes.len() - 1;
+    let atm = strikes.iter().rposition(|k| *k <= forward).unwrap_or(0);
+
+    // replication of log contract: -ln(S/F) = -(S - K0) / K0 + ln(K0 / F) + integral of OTM payoffs / K^2
+    let mut integral = 0.0;
+    for (i, k) in strikes.iter().enumerate() {
+        // strike interval, one sided at the ends of the strip
+        let width = strikes[last.min(i + 1)] - strikes[i.saturating_sub(1)];
+        let dk = if i == 0 || i == last {width} else {width / 2.0};
+        let otm = match i.cmp(&atm) {
+            std::cmp::Ordering::Less => strip.put_premiums[i],
+            std::cmp::Ordering::Equal => (strip.put_premiums[i] + strip.call_premiums[i]) / 2.0,
+            std::cmp::Ordering::Greater => strip.call_premiums[i]
+        };
+        integral += dk * otm / (k * k);
+    }
+
+    let k0 = strikes[atm];
+    2.0 / t * ((bs_params.rate * t).exp() * integral - (forward / k0 - 1.0 - (forward / k0).ln()))
+}
+
+/// Volatility swap fair strike from variance swap fair strike with convexity adjustment
+/// `E[sqrt(V)] ~ sqrt(E[V]) - Var[V] / (8 E[V]^(3/2))` (Brockhaus and Long)
+/// * variance_of_variance - variance of realized annualized variance until expiry
+pub fn volatility_swap_strike(fair_variance: f64, variance_of_variance: f64) -> f64 {
+    fair_variance.sqrt() - variance_of_variance / (8.0 * fair_variance.powf(1.5))
+}
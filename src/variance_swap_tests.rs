@@ -0,0 +1,56 @@
+#![cfg(test)]
+use crate::black_scholes::*;
+use crate::variance_swap::*;
+
+fn market() -> BlackScholesParams {
+    BlackScholesParams { price: 100.0, strike: 0.0, rate: 0.04, div_yield: 0.015, vol: 0.25, time_to_expiry: 0.75 }
+}
+
+fn strikes(from: f64, to: f64, step: f64) -> Vec<f64> {
+    let n = ((to - from) / step).round() as usize;
+    (0..=n).map(|i| from + i as f64 * step).collect()
+}
+
+#[test]
+fn test_flat_volatility() {
+    // without smile fair variance is the implied variance
+    let p = market();
+    for vol in [0.1, 0.25, 0.4] {
+        let strip = OptionStrip::from_model(&p, &strikes(5.0, 500.0, 0.5), |_| vol).unwrap();
+        assert!((variance_swap_strike(&p, &strip) - vol * vol).abs() < 0.0002);
+    }
+}
+
+#[test]
+fn test_market_quotes() {
+    let p = market();
+    let ks = strikes(10.0, 400.0, 1.0);
+    let smile = |k: f64| 0.25 - 0.1 * (k / 100.0).ln();
+    let model = OptionStrip::from_model(&p, &ks, smile).unwrap();
+    let quotes = OptionStrip::new(&ks, &model.put_premiums, &model.call_premiums).unwrap();
+    let fair = variance_swap_strike(&p, &quotes);
+    assert_eq!(fair, variance_swap_strike(&p, &model));
+
+    // negative skew makes variance more expensive than at-the-money variance
+    assert!(fair > 0.25 * 0.25);
+    // result converges with finer strip
+    let fine = OptionStrip::from_model(&p, &strikes(10.0, 400.0, 0.25), smile).unwrap();
+    assert!((variance_swap_strike(&p, &fine) - fair).abs() < 0.0002);
+    // and does not depend on where the forward is in the strike grid
+    let shifted = OptionStrip::from_model(&p, &strikes(10.3, 400.3, 1.0), smile).unwrap();
+    assert!((variance_swap_strike(&p, &shifted) - fair).abs() < 0.0002);
+
+    assert!(OptionStrip::new(&[100.0, 90.0], &[1.0, 2.0], &[2.0, 1.0]).is_err());
+    assert!(OptionStrip::new(&[90.0, 100.0], &[1.0], &[2.0, 1.0]).is_err());
+}
+
+#[test]
+fn test_volatility_swap() {
+    assert_eq!(volatility_swap_strike(0.04, 0.0), 0.2);
+    // lognormal variance with known moments: E[sqrt(V)] = sqrt(E[V]) * exp(-s^2 / 8), where s^2 is variance of ln V
+    let (mean, s2): (f64, f64) = (0.04, 0.05);
+    let exact = mean.sqrt() * (-s2 / 8.0).exp();
+    let approx = volatility_swap_strike(mean, mean * mean * (s2.exp() - 1.0));
+    assert!(approx < mean.sqrt());
+    assert!((approx - exact).abs() < 0.0005);
+}
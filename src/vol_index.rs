@@ -0,0 +1,118 @@
+//! module with volatility index calculated with CBOE VIX methodology from put and call quotes of two expiries:
+//! forward from put/call parity at the strike with the smallest call-put difference, at-the-money strike `K0`
+//! below the forward, out-of-the-money options weighted by strike interval and interpolation of variance
+//! to the constant term of the index.
+use std::cmp::Ordering;
+use crate::black_scholes::*;
+
+/// Term of VIX index in years
+pub const VIX_TERM: f64 = 30.0 / 365.0;
+
+/// Bid and ask quotes of call and put with the same strike
+#[derive(Debug, Clone, Copy)]
+pub struct ChainQuote {
+    pub strike: f64,
+    pub call_bid: f64,
+    pub call_ask: f64,
+    pub put_bid: f64,
+    pub put_ask: f64
+}
+
+/// Option chain of one expiry
+#[derive(Debug, Clone)]
+pub struct ExpiryChain {
+    /// time to expiry in years
+    pub time_to_expiry: f64,
+    /// risk free rate to expiry
+    pub rate: f64,
+    pub quotes: Vec<ChainQuote>
+}
+
+/// Intermediate results for one expiry
+#[derive(Debug, Clone, Copy)]
+pub struct ExpiryVariance {
+    pub forward: f64,
+    /// first strike not above the forward
+    pub atm_strike: f64,
+    /// annualized variance
+    pub variance: f64,
+    /// number of options included in the variance
+    pub options: usize
+}
+
+/// Variance of one expiry from out-of-the-money options, options with zero bid are excluded and
+/// options beyond two consecutive zero bids are not used
+pub fn expiry_variance(chain: &ExpiryChain) -> Result<ExpiryVariance, String> {
+    let t = chain.time_to_expiry;
+    if t <= 0.0 {
+        return Err("time to expiry must be positive".to_string());
+    }
+    let mut quotes = chain.quotes.clone();
+    quotes.sort_by(|a, b| a.strike.partial_cmp(&b.strike).unwrap_or(Ordering::Equal));
+    let call_mid = |q: &ChainQuote| (q.call_bid + q.call_ask) / 2.0;
+    let put_mid = |q: &ChainQuote| (q.put_bid + q.put_ask) / 2.0;
+
+    // forward from put/call parity, with zero spot parity gives C + K exp(-rT) = P + F exp(-rT)
+    let parity = quotes.iter()
+        .filter(|q| q.call_bid > 0.0 && q.put_bid > 0.0)
+        .min_by(|a, b| (call_mid(a) - put_mid(a)).abs().partial_cmp(&(call_mid(b) - put_mid(b)).abs()).unwrap_or(Ordering::Equal))
+        .ok_or_else(|| "no strike with both call and put bid".to_string())?;
+    let bs_params = BlackScholesParams { price: 0.0, strike: parity.strike, div_yield: 0.0, rate: chain.rate, vol: 0.0, time_to_expiry: t };
+    let forward = (chain.rate * t).exp() * (callput_price(true, &call_mid(parity), &bs_params) - put_mid(parity));
+
+    let atm = quotes.iter().rposition(|q| q.strike <= forward)
+        .ok_or_else(|| "no strike below the forward".to_string())?;
+
+    // out-of-the-money options from at-the-money strike outwards, until two consecutive zero bids
+    let mut selected = vec![(atm, (call_mid(&quotes[atm]) + put_mid(&quotes[atm])) / 2.0)];
+    for (range, is_call) in [((0..atm).rev().collect::<Vec<usize>>(), false), ((atm + 1..quotes.len()).collect(), true)] {
+        let mut zero_bids = 0;
+        for i in range {
+            let (bid, mid) = if is_call {(quotes[i].call_bid, call_mid(&quotes[i]))} else {(quotes[i].put_bid, put_mid(&quotes[i]))};
+            if bid > 0.0 {
+                zero_bids = 0;
+                selected.push((i, mid));
+            } else {
+                zero_bids += 1;
+                if zero_bids == 2 {
+                    break;
+                }
+            }
+        }
+    }
+    selected.sort_by_key(|(i, _)| *i);
+    if selected.len() < 2 {
+        return Err("at least two out-of-the-money options are needed".to_string());
+    }
+
+    let strikes: Vec<f64> = selected.iter().map(|(i, _)| quotes[*i].strike).collect();
+    let last = strikes.len() - 1;
+    let mut sum = 0.0;
+    for (j, (_, mid)) in selected.iter().enumerate() {
+        // strike interval, one sided at the ends of included strikes
+        let width = strikes[last.min(j + 1)] - strikes[j.saturating_sub(1)];
+        let dk = if j == 0 || j == last {width} else {width / 2.0};
+        sum += dk / (strikes[j] * strikes[j]) * mid;
+    }
+
+    let k0 = quotes[atm].strike;
+    let variance = 2.0 / t * (chain.rate * t).exp() * sum - (forward / k0 - 1.0).powi(2) / t;
+    Ok(ExpiryVariance { forward, atm_strike: k0, variance, options: selected.len() })
+}
+
+/// Volatility index in volatility points, interpolating total variance of near and next expiries
+/// to `target_time` in years (e.g. [`VIX_TERM`])
+pub fn volatility_index(near: &ExpiryChain, next: &ExpiryChain, target_time: f64) -> Result<f64, String> {
+    let (t1, t2) = (near.time_to_expiry, next.time_to_expiry);
+    if t1 >= t2 {
+        return Err("near expiry must be before next expiry".to_string());
+    }
+    let v1 = expiry_variance(near)?.variance;
+    let v2 = expiry_variance(next)?.variance;
+    let w = (t2 - target_time) / (t2 - t1);
+    let variance = (w * t1 * v1 + (1.0 - w) * t2 * v2) / target_time;
+    if variance < 0.0 {
+        return Err("interpolated variance is negative".to_string());
+    }
+    Ok(100.0 * variance.sqrt())
+}
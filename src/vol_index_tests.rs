@@ -0,0 +1,72 @@
+#![cfg(test)]
+use crate::black_scholes::*;
+use crate::vol_index::*;
+
+const SPOT: f64 = 4500.0;
+const DIV: f64 = 0.015;
+
+/// Chain priced with Black/Scholes formulas with volatility depending on strike and fixed relative bid/ask spread,
+/// options worth less than 0.05 have zero bid
+fn chain<F>(time_to_expiry: f64, rate: f64, strikes: &[f64], vol: F) -> ExpiryChain
+    where F: Fn(f64) -> f64 {
+    let quote = |premium: f64| if premium < 0.05 {(0.0, 0.05)} else {(premium * 0.99, premium * 1.01)};
+    let quotes = strikes.iter().map(|k| {
+        let p = BlackScholesParams { price: SPOT, strike: *k, div_yield: DIV, rate, vol: vol(*k), time_to_expiry };
+        let (call_bid, call_ask) = quote(call_premium(&p));
+        let (put_bid, put_ask) = quote(put_premium(&p));
+        ChainQuote { strike: *k, call_bid, call_ask, put_bid, put_ask }
+    }).collect();
+    ExpiryChain { time_to_expiry, rate, quotes }
+}
+
+fn strikes() -> Vec<f64> {
+    (0..=240).map(|i| 1500.0 + 25.0 * i as f64).collect()
+}
+
+#[test]
+fn test_expiry_variance() {
+    let (t, r) = (25.0 / 365.0, 0.05);
+    let result = expiry_variance(&chain(t, r, &strikes(), |_| 0.2)).unwrap();
+    let forward = SPOT * ((r - DIV) * t).exp();
+    assert!((result.forward - forward).abs() < 0.5);
+    assert!(result.atm_strike <= forward && result.atm_strike + 25.0 > forward);
+    assert!((result.variance.sqrt() - 0.2).abs() < 0.003);
+    // far out-of-the-money options with zero bids are dropped
+    assert!(result.options < strikes().len());
+}
+
+#[test]
+fn test_volatility_index() {
+    let (t1, t2) = (23.0 / 365.0, 37.0 / 365.0);
+    let near = chain(t1, 0.05, &strikes(), |_| 0.18);
+    let next = chain(t2, 0.05, &strikes(), |_| 0.18);
+    assert!((volatility_index(&near, &next, VIX_TERM).unwrap() - 18.0).abs() < 0.3);
+
+    // term structure: index is between volatilities of the expiries
+    let next = chain(t2, 0.05, &strikes(), |_| 0.24);
+    let index = volatility_index(&near, &next, VIX_TERM).unwrap();
+    assert!(index > 19.0 && index < 23.5);
+
+    // negative skew increases the index
+    let skew = |k: f64| 0.18 - 0.3 * (k / SPOT).ln();
+    let skewed = volatility_index(&chain(t1, 0.05, &strikes(), skew), &chain(t2, 0.05, &strikes(), skew), VIX_TERM).unwrap();
+    assert!(skewed > 18.0);
+
+    assert!(volatility_index(&next, &near, VIX_TERM).is_err());
+}
+
+#[test]
+fn test_zero_bid_truncation() {
+    // strikes beyond two consecutive zero bids are not used even if they have bids
+    let t = 30.0 / 365.0;
+    let mut with_outlier = chain(t, 0.05, &strikes(), |_| 0.2);
+    let base = expiry_variance(&with_outlier).unwrap();
+    with_outlier.quotes[0].put_bid = 10.0;
+    with_outlier.quotes[0].put_ask = 12.0;
+    let result = expiry_variance(&with_outlier).unwrap();
+    assert_eq!(result.options, base.options);
+    assert_eq!(result.variance, base.variance);
+
+    let empty = ExpiryChain { time_to_expiry: t, rate: 0.05, quotes: vec![] };
+    assert!(expiry_variance(&empty).is_err());
+}